@@ -1,7 +1,7 @@
 #![feature(generic_arg_infer)]
 
 use measure::Measurable;
-use tensor::Shape;
+use tensor::{Shape, Tensor};
 use cognitio::prelude::*;
 
 fn main() {
@@ -24,7 +24,7 @@ fn main() {
         .add_layer(pool(2, 2))
         .add_layer(dense::Builder::new().activation(Sigmoid::new()).output_shape([16].into()))
         .add_layer(dense::Builder::new()
-            .activation(Sigmoid::new())
+            .activation(SoftMax::new())
             .kernel_initializer(VarianceScaling::normal(0.1, Mode::FanIn))
             .output_shape([10].into()))
         // .add_layer(softmax::Builder::new())
@@ -33,11 +33,11 @@ fn main() {
     let dataset = MNIST::offline("../training", "../testing").unwrap();
     let optimizer = Adam::new(0.9, 0.99, 0.1).batch(10);
     let mut trainer = m.compile(optimizer);
-    let ((), t) = (|| trainer.train(1, &dataset, Square::new(), |l| {
-        let mut t = [0.; 10];
-        t[*l as usize] = 1.;
-        Shape::new([10]).into_tensor(|i| t[i])
-        // Tensor::from(t)
+    let (history, t) = (|| trainer.train(1, &dataset, SparseCategoricalCrossentropy::new(false, 0.), |l| {
+        Tensor::new(*l as usize, Shape::new([]))
     })).measure();
+    for (epoch, loss) in history.iter().enumerate() {
+        println!("epoch {}: loss {loss:.4}", epoch + 1);
+    }
     println!("{t:?}");
 }
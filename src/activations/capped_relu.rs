@@ -0,0 +1,47 @@
+use num_traits::Float;
+
+use crate::activations::Activation;
+
+/// Rectified Linear Unit [`Activation<T>`] function capped at `cap`
+/// ```text
+///                   0   ; x <= 0
+/// capped_relu(x) =  x   ; 0 < x < cap
+///                   cap ; x >= cap
+/// ```
+pub struct CappedReLU<T: Float> {
+    cap: T,
+}
+
+impl<T: Float> CappedReLU<T> {
+    /// [`CappedReLU<T>`] constructor
+    pub const fn new(cap: T) -> Self {
+        Self { cap }
+    }
+}
+
+impl<T: Float + From<i32>> CappedReLU<T> {
+    /// [`CappedReLU<T>`] capped at 6
+    pub fn relu6() -> Self {
+        Self::new(T::from(6))
+    }
+}
+
+impl<T: Float + From<i32>> Activation<T> for CappedReLU<T> {
+    fn activate(&self, x: T) -> T {
+        if x <= T::zero() {
+            T::zero()
+        } else if x >= self.cap {
+            self.cap
+        } else {
+            x
+        }
+    }
+
+    fn derive(&self, x: T) -> T {
+        if x > T::zero() && x < self.cap {
+            T::from(1)
+        } else {
+            T::zero()
+        }
+    }
+}
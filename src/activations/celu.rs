@@ -0,0 +1,37 @@
+use num_traits::Float;
+
+use crate::activations::Activation;
+
+/// Continuously differentiable Exponential Linear Unit [`Activation<T>`] function
+/// ```text
+///            alpha*(e^(x/alpha) - 1) ; x <= 0
+/// celu(x) =  x                       ; x > 0
+/// ```
+pub struct CELU<T: Float> {
+    alpha: T,
+}
+
+impl<T: Float> CELU<T> {
+    /// [`CELU<T>`] constructor
+    pub const fn new(alpha: T) -> Self {
+        Self { alpha }
+    }
+}
+
+impl<T: Float + From<i32>> Activation<T> for CELU<T> {
+    fn activate(&self, x: T) -> T {
+        if x > T::zero() {
+            x
+        } else {
+            self.alpha * ((x / self.alpha).exp() - T::from(1))
+        }
+    }
+
+    fn derive(&self, x: T) -> T {
+        if x > T::zero() {
+            T::from(1)
+        } else {
+            (x / self.alpha).exp()
+        }
+    }
+}
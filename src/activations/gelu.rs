@@ -0,0 +1,87 @@
+use std::marker::PhantomData;
+
+use num_traits::Float;
+
+use crate::activations::{Activation, ratio};
+
+/// Gaussian Error Linear Unit [`Activation<T>`] function
+/// ```text
+/// gelu(x) = x * (1 + erf(x / sqrt(2))) / 2
+///
+/// approximated:
+/// gelu(x) = x * (1 + tanh(sqrt(2 / pi) * (x + 0.044715 * x^3))) / 2
+/// ```
+pub struct GELU<T> {
+    approximate: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T> GELU<T> {
+    /// [`GELU<T>`] constructor
+    pub const fn new(approximate: bool) -> Self {
+        Self {
+            approximate,
+            _marker: PhantomData,
+        }
+    }
+
+    /// [`GELU<T>`] computed with `erf`
+    pub const fn exact() -> Self {
+        Self::new(false)
+    }
+
+    /// [`GELU<T>`] approximated with `tanh`
+    pub const fn tanh() -> Self {
+        Self::new(true)
+    }
+}
+
+impl<T> Default for GELU<T> {
+    fn default() -> Self {
+        Self::exact()
+    }
+}
+
+/// `0.044715`
+const COEFFICIENT: (i32, i32) = (44715, 1000000);
+
+/// `pi`, to nine decimal places
+const PI: (i32, i32) = (314159265, 100000000);
+
+/// Abramowitz and Stegun approximation of the error function, accurate to `1.5e-7`
+fn erf<T: Float + From<i32>>(x: T) -> T {
+    let t = T::from(1) / (T::from(1) + ratio::<T>(3275911, 10000000) * x.abs());
+    let polynomial = [1061405429, -1453152027, 1421413741, -284496736, 254829592]
+        .into_iter()
+        .fold(T::zero(), |p, a| (p + ratio(a, 1000000000)) * t);
+    let y = T::from(1) - polynomial * (-x * x).exp();
+    if x < T::zero() { -y } else { y }
+}
+
+impl<T: Float + From<i32>> GELU<T> {
+    fn inner(&self, x: T) -> T {
+        (T::from(2) / ratio(PI.0, PI.1)).sqrt() * (x + ratio::<T>(COEFFICIENT.0, COEFFICIENT.1) * x * x * x)
+    }
+}
+
+impl<T: Float + From<i32>> Activation<T> for GELU<T> {
+    fn activate(&self, x: T) -> T {
+        if self.approximate {
+            x * (T::from(1) + self.inner(x).tanh()) / T::from(2)
+        } else {
+            x * (T::from(1) + erf(x / T::from(2).sqrt())) / T::from(2)
+        }
+    }
+
+    fn derive(&self, x: T) -> T {
+        if self.approximate {
+            let tanh = self.inner(x).tanh();
+            let inner_d = (T::from(2) / ratio(PI.0, PI.1)).sqrt() * (T::from(1) + T::from(3) * ratio(COEFFICIENT.0, COEFFICIENT.1) * x * x);
+            (T::from(1) + tanh) / T::from(2) + x * (T::from(1) - tanh * tanh) * inner_d / T::from(2)
+        } else {
+            let cdf = (T::from(1) + erf(x / T::from(2).sqrt())) / T::from(2);
+            let pdf = (-x * x / T::from(2)).exp() / (T::from(2) * ratio(PI.0, PI.1)).sqrt();
+            cdf + x * pdf
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::marker::PhantomData;
+
+use num_traits::Float;
+
+use crate::activations::Activation;
+
+/// Piecewise linear approximation of the [`Sigmoid<T>`] [`Activation<T>`] function
+/// ```text
+///                    0           ; x <= -3
+/// hard_sigmoid(x) =  x / 6 + 1/2 ; -3 < x < 3
+///                    1           ; x >= 3
+/// ```
+///
+/// [`Sigmoid<T>`]: crate::activations::sigmoid::Sigmoid
+pub struct HardSigmoid<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> HardSigmoid<T> {
+    /// [`HardSigmoid<T>`] constructor
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<T> Default for HardSigmoid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float + From<i32>> Activation<T> for HardSigmoid<T> {
+    fn activate(&self, x: T) -> T {
+        if x <= T::from(-3) {
+            T::zero()
+        } else if x >= T::from(3) {
+            T::from(1)
+        } else {
+            x / T::from(6) + T::from(1) / T::from(2)
+        }
+    }
+
+    fn derive(&self, x: T) -> T {
+        if x > T::from(-3) && x < T::from(3) {
+            T::from(1) / T::from(6)
+        } else {
+            T::zero()
+        }
+    }
+}
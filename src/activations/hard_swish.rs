@@ -0,0 +1,47 @@
+use std::marker::PhantomData;
+
+use num_traits::Float;
+
+use crate::activations::{
+    Activation,
+    hard_sigmoid::HardSigmoid,
+};
+
+/// Piecewise approximation of the [`Swish<T>`] [`Activation<T>`] function
+/// ```text
+/// hard_swish(x) = x * hard_sigmoid(x)
+/// ```
+///
+/// [`Swish<T>`]: crate::activations::swish::Swish
+pub struct HardSwish<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> HardSwish<T> {
+    /// [`HardSwish<T>`] constructor
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<T> Default for HardSwish<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float + From<i32>> Activation<T> for HardSwish<T> {
+    fn activate(&self, x: T) -> T {
+        x * HardSigmoid::new().activate(x)
+    }
+
+    fn derive(&self, x: T) -> T {
+        if x <= T::from(-3) {
+            T::zero()
+        } else if x >= T::from(3) {
+            T::from(1)
+        } else {
+            (T::from(2) * x + T::from(3)) / T::from(6)
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use num_traits::Float;
+
+use crate::activations::Activation;
+
+/// Leaky Rectified Linear Unit [`Activation<T>`] function
+/// ```text
+///                 alpha*x ; x <= 0
+/// leaky_relu(x) = x       ; x > 0
+/// ```
+pub struct LeakyReLU<T: Float> {
+    alpha: T,
+}
+
+impl<T: Float> LeakyReLU<T> {
+    /// [`LeakyReLU<T>`] constructor
+    pub const fn new(alpha: T) -> Self {
+        Self { alpha }
+    }
+}
+
+impl<T: Float + From<i32>> Activation<T> for LeakyReLU<T> {
+    fn activate(&self, x: T) -> T {
+        if x > T::zero() {
+            x
+        } else {
+            self.alpha * x
+        }
+    }
+
+    fn derive(&self, x: T) -> T {
+        if x > T::zero() {
+            T::from(1)
+        } else {
+            self.alpha
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::marker::PhantomData;
+
+use num_traits::Float;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    activations::{Elementwise, TensorActivation},
+    layers::fibers,
+    losses::log_sum_exp,
+};
+
+/// Logarithm of the [`SoftMax<T>`] [`TensorActivation<T, B, N>`] function over the trailing axis
+/// ```text
+/// log_softmax(x)[i] = x[i] - ln(sum(e^x[j]))
+/// ```
+///
+/// [`SoftMax<T>`]: crate::activations::softmax::SoftMax
+pub struct LogSoftMax<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> LogSoftMax<T> {
+    /// [`LogSoftMax<T>`] constructor
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<T> Default for LogSoftMax<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> !Elementwise for LogSoftMax<T> {}
+
+impl<T: Float, B: BackendProvider, const N: usize> TensorActivation<T, B, N> for LogSoftMax<T> {
+    type Parameters = [Void; 0];
+    /// Probabilities of the softmax
+    type State = Vec<T>;
+
+    fn pre_activation_shape(&mut self, output_shape: &Shape<N>) -> Shape<N> {
+        output_shape.clone()
+    }
+
+    fn forward(&self, z: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::State) {
+        let mut output = Tensor::new(T::zero(), z.shape().clone());
+        fibers(z.shape(), Some(N - 1)).for_each(|f| {
+            let log_sum = log_sum_exp(f.clone().map(|i| z[i]));
+            f.for_each(|i| output[i] = z[i] - log_sum);
+        });
+        let state = output.iter().map(|t| t.exp()).collect();
+        (output, state)
+    }
+
+    fn backward(&self, state: Self::State, d: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::Parameters) {
+        let mut z_d = Tensor::new(T::zero(), d.shape().clone());
+        fibers(d.shape(), Some(N - 1)).for_each(|f| {
+            let sum = f.clone().map(|i| d[i]).sum::<T>();
+            f.for_each(|i| z_d[i] = d[i] - state[i] * sum);
+        });
+        (z_d, [])
+    }
+
+    fn update(&mut self, []: &Self::Parameters) {}
+}
@@ -0,0 +1,93 @@
+use std::{
+    array,
+    marker::PhantomData,
+};
+
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::activations::{Elementwise, TensorActivation};
+
+/// MaxOut [`TensorActivation<T, B, N>`] function
+///
+/// The host layer computes `pieces` pre-activations per output unit and the maximum of them is kept.
+/// ```text
+/// maxout(x)[i] = max(x[i * pieces + p])
+/// ```
+pub struct MaxOut<T> {
+    pieces: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> MaxOut<T> {
+    /// [`MaxOut<T>`] constructor
+    pub const fn new(pieces: usize) -> Self {
+        assert!(pieces > 0);
+        Self {
+            pieces,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> !Elementwise for MaxOut<T> {}
+
+impl<T: Number, B: BackendProvider, const N: usize> TensorActivation<T, B, N> for MaxOut<T> {
+    type Parameters = [Void; 0];
+    /// Shape of the pre-activations and the index of the maximum of every output
+    type State = (Shape<N>, Vec<usize>);
+
+    fn pre_activation_shape(&mut self, output_shape: &Shape<N>) -> Shape<N> {
+        Shape::new(array::from_fn(|i| if i + 1 == N {
+            output_shape[i] * self.pieces
+        } else {
+            output_shape[i]
+        }))
+    }
+
+    fn forward(&self, z: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::State) {
+        let output_shape = Shape::new(array::from_fn(|i| if i + 1 == N {
+            z.shape()[i] / self.pieces
+        } else {
+            z.shape()[i]
+        }));
+        let indices = (0..output_shape.capacity())
+            .map(|o| (o * self.pieces..(o + 1) * self.pieces)
+                .reduce(|m, i| if z[i] > z[m] { i } else { m })
+                .unwrap())
+            .collect::<Vec<_>>();
+        let output = output_shape.into_tensor(|o| z[indices[o]]);
+        (output, (z.shape().clone(), indices))
+    }
+
+    fn backward(&self, (shape, indices): Self::State, d: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::Parameters) {
+        let mut z_d = Tensor::new(T::zero(), shape);
+        indices.into_iter().enumerate().for_each(|(o, i)| z_d[i] = d[o]);
+        (z_d, [])
+    }
+
+    fn update(&mut self, []: &Self::Parameters) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        activations::TensorActivation,
+        testing::{check_activation_gradient, tensor, values},
+    };
+
+    use super::MaxOut;
+
+    #[test]
+    fn keeps_the_maximum_of_every_group() {
+        let (output, _) = MaxOut::new(2).forward(tensor([1, 4], &[1., 3., -2., -4.]));
+        assert_eq!(output.shape().capacity(), 2);
+        assert_eq!((output[0], output[1]), (3., -2.));
+    }
+
+    #[test]
+    fn gradient() {
+        check_activation_gradient(&MaxOut::new(3), tensor([2, 6], &values(12, 2)));
+    }
+}
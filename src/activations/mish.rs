@@ -0,0 +1,38 @@
+use std::marker::PhantomData;
+
+use num_traits::Float;
+
+use crate::activations::Activation;
+
+/// Mish [`Activation<T>`] function
+/// ```text
+/// mish(x) = x * tanh(ln(1 + e^x))
+/// ```
+pub struct Mish<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> Mish<T> {
+    /// [`Mish<T>`] constructor
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<T> Default for Mish<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float + From<i32>> Activation<T> for Mish<T> {
+    fn activate(&self, x: T) -> T {
+        x * (x.exp() + T::from(1)).ln().tanh()
+    }
+
+    fn derive(&self, x: T) -> T {
+        let tanh = (x.exp() + T::from(1)).ln().tanh();
+        let sigmoid = T::from(1) / (T::from(1) + (-x).exp());
+        tanh + x * (T::from(1) - tanh * tanh) * sigmoid
+    }
+}
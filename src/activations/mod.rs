@@ -1,13 +1,30 @@
 //! Trait that defines [`Activation<T>`] functions
 
+use num_traits::{Float, Number};
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+pub mod capped_relu;
+pub mod celu;
 pub mod elu;
 pub mod exp;
+pub mod gelu;
+pub mod hard_sigmoid;
+pub mod hard_swish;
 pub mod identity;
+pub mod leaky_relu;
 pub mod linear;
+pub mod log_softmax;
+pub mod maxout;
+pub mod mish;
+pub mod parametric;
 pub mod relu;
+pub mod selu;
 pub mod sigmoid;
+pub mod softmax;
 pub mod softplus;
 pub mod softsign;
+pub mod sparsemax;
 pub mod swish;
 pub mod tanh;
 
@@ -17,3 +34,114 @@ pub trait Activation<T> {
 
     fn derive(&self, x: T) -> T;
 }
+
+/// `numerator / denominator` for activations that only require `T: From<i32>`
+pub(crate) fn ratio<T: Float + From<i32>>(numerator: i32, denominator: i32) -> T {
+    T::from(numerator) / T::from(denominator)
+}
+
+/// Implemented by every type without trainable parameters
+pub auto trait Stateless {}
+
+/// Activation with trainable parameters that are stored per channel
+///
+/// Host layers call [`build`](Parametric::build) with the size of their trailing channel axis
+/// and add [`Parameters`](Parametric::Parameters) to their `Internal`.
+pub trait Parametric<T> {
+    type Parameters;
+
+    fn build(&mut self, channels: usize);
+
+    fn activate_at(&self, channel: usize, x: T) -> T;
+
+    fn derive_at(&self, channel: usize, x: T) -> T;
+
+    /// Takes `(channel, x, d)` for every activated value, where `d` is the derivative with respect to the output
+    fn derive_parameters<I: Iterator<Item=(usize, T, T)>>(&self, iter: I) -> Self::Parameters;
+
+    fn update(&mut self, update: &Self::Parameters);
+}
+
+impl<T, A: Activation<T> + Stateless> Parametric<T> for A {
+    type Parameters = [Void; 0];
+
+    fn build(&mut self, _: usize) {}
+
+    fn activate_at(&self, _: usize, x: T) -> T {
+        self.activate(x)
+    }
+
+    fn derive_at(&self, _: usize, x: T) -> T {
+        self.derive(x)
+    }
+
+    fn derive_parameters<I: Iterator<Item=(usize, T, T)>>(&self, _: I) -> Self::Parameters {
+        []
+    }
+
+    fn update(&mut self, []: &Self::Parameters) {}
+}
+
+/// Replaces the pre-activations of a channel-last tensor with the derivatives with respect to them
+/// and returns the derivatives with respect to the parameters of `activation`
+///
+/// `output_d` gives the derivative with respect to the output at a linear index.
+pub(crate) fn back_propagate<T: Number, B: BackendProvider, A: Parametric<T>, F: Fn(usize) -> T, const N: usize>(
+    activation: &A,
+    pre_activations: &mut Tensor<T, B, N>,
+    output_d: F,
+) -> A::Parameters {
+    let channels = pre_activations.shape()[N - 1];
+    let parameters_d = activation.derive_parameters(
+        pre_activations.iter().enumerate().map(|(i, x)| (i % channels, *x, output_d(i)))
+    );
+    pre_activations
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, x)| *x = activation.derive_at(i % channels, *x) * output_d(i));
+    parameters_d
+}
+
+/// Implemented by every activation that is applied to each value independently
+pub auto trait Elementwise {}
+
+/// Activation applied to a whole pre-activation tensor at once
+pub trait TensorActivation<T, B: BackendProvider, const N: usize> {
+    type Parameters;
+    /// Whatever [`forward`](TensorActivation::forward) has to keep for [`backward`](TensorActivation::backward)
+    type State;
+
+    /// Called once by the host layer, returns the shape of the pre-activations needed for `output_shape`
+    fn pre_activation_shape(&mut self, output_shape: &Shape<N>) -> Shape<N>;
+
+    fn forward(&self, z: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::State);
+
+    /// Takes the derivative with respect to the output and returns the one with respect to the pre-activations
+    fn backward(&self, state: Self::State, d: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::Parameters);
+
+    fn update(&mut self, update: &Self::Parameters);
+}
+
+impl<T: Number, B: BackendProvider, A: Parametric<T> + Elementwise, const N: usize> TensorActivation<T, B, N> for A {
+    type Parameters = A::Parameters;
+    type State = Tensor<T, B, N>;
+
+    fn pre_activation_shape(&mut self, output_shape: &Shape<N>) -> Shape<N> {
+        self.build(output_shape[N - 1]);
+        output_shape.clone()
+    }
+
+    fn forward(&self, z: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::State) {
+        let channels = z.shape()[N - 1];
+        (z.shape().clone().into_tensor(|i| self.activate_at(i % channels, z[i])), z)
+    }
+
+    fn backward(&self, mut state: Self::State, d: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::Parameters) {
+        let parameters_d = back_propagate(self, &mut state, |i| d[i]);
+        (state, parameters_d)
+    }
+
+    fn update(&mut self, update: &Self::Parameters) {
+        Parametric::update(self, update)
+    }
+}
@@ -0,0 +1,56 @@
+use num_traits::Float;
+use tensor::{BackendProvider, Shape, Tensor};
+
+use crate::activations::{Parametric, Stateless};
+
+/// Exponential Linear Unit [`Parametric<T>`] function with a trainable `alpha`
+/// ```text
+///            alpha*(e^x - 1) ; x <= 0
+/// pelu(x) =  x               ; x > 0
+/// ```
+pub struct PELU<T, B: BackendProvider> {
+    alpha: Tensor<T, B, 0>,
+}
+
+impl<T: Float, B: BackendProvider> PELU<T, B> {
+    /// [`PELU<T, B>`] constructor
+    pub fn new(alpha: T) -> Self {
+        Self { alpha: Tensor::new(alpha, Shape::zero()) }
+    }
+}
+
+impl<T, B: BackendProvider> !Stateless for PELU<T, B> {}
+
+impl<T: Float + From<i32>, B: BackendProvider> Parametric<T> for PELU<T, B> {
+    type Parameters = Tensor<T, B, 0>;
+
+    fn build(&mut self, _: usize) {}
+
+    fn activate_at(&self, _: usize, x: T) -> T {
+        if x > T::zero() {
+            x
+        } else {
+            self.alpha[0] * (x.exp() - T::from(1))
+        }
+    }
+
+    fn derive_at(&self, _: usize, x: T) -> T {
+        if x > T::zero() {
+            T::from(1)
+        } else {
+            self.alpha[0] * x.exp()
+        }
+    }
+
+    fn derive_parameters<I: Iterator<Item=(usize, T, T)>>(&self, iter: I) -> Self::Parameters {
+        let alpha_d = iter
+            .filter(|(_, x, _)| *x <= T::zero())
+            .map(|(_, x, d)| (x.exp() - T::from(1)) * d)
+            .sum::<T>();
+        Tensor::new(alpha_d, Shape::zero())
+    }
+
+    fn update(&mut self, update: &Self::Parameters) {
+        self.alpha[0] = self.alpha[0] - update[0];
+    }
+}
@@ -0,0 +1,7 @@
+//! [`Parametric<T>`] activations with trainable parameters
+//!
+//! [`Parametric<T>`]: crate::activations::Parametric
+
+pub mod elu;
+pub mod prelu;
+pub mod swish;
@@ -0,0 +1,63 @@
+use num_traits::Float;
+use tensor::{BackendProvider, Shape, Tensor};
+
+use crate::activations::{Parametric, Stateless};
+
+/// Parametric Rectified Linear Unit [`Parametric<T>`] function with a trainable slope per channel
+/// ```text
+///                 alpha[c]*x ; x <= 0
+/// prelu(c, x) =   x          ; x > 0
+/// ```
+pub struct PReLU<T, B: BackendProvider> {
+    initial: T,
+    alpha: Tensor<T, B, 1>,
+}
+
+impl<T: Float, B: BackendProvider> PReLU<T, B> {
+    /// [`PReLU<T, B>`] constructor, every slope starts at `initial`
+    pub fn new(initial: T) -> Self {
+        Self {
+            initial,
+            alpha: Tensor::new(initial, Shape::new([0])),
+        }
+    }
+}
+
+impl<T, B: BackendProvider> !Stateless for PReLU<T, B> {}
+
+impl<T: Float + From<i32>, B: BackendProvider> Parametric<T> for PReLU<T, B> {
+    type Parameters = Tensor<T, B, 1>;
+
+    fn build(&mut self, channels: usize) {
+        self.alpha = Tensor::new(self.initial, Shape::new([channels]));
+    }
+
+    fn activate_at(&self, channel: usize, x: T) -> T {
+        if x > T::zero() {
+            x
+        } else {
+            self.alpha[channel] * x
+        }
+    }
+
+    fn derive_at(&self, channel: usize, x: T) -> T {
+        if x > T::zero() {
+            T::from(1)
+        } else {
+            self.alpha[channel]
+        }
+    }
+
+    fn derive_parameters<I: Iterator<Item=(usize, T, T)>>(&self, iter: I) -> Self::Parameters {
+        let mut alpha_d = Tensor::new(T::zero(), self.alpha.shape().clone());
+        iter.filter(|(_, x, _)| *x <= T::zero()).for_each(|(c, x, d)| alpha_d[c] += x * d);
+        alpha_d
+    }
+
+    fn update(&mut self, update: &Self::Parameters) {
+        self.alpha
+            .iter_mut()
+            .zip(update.iter().copied())
+            .for_each(|(a, b)| *a = *a - b);
+    }
+}
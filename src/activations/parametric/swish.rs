@@ -0,0 +1,54 @@
+use num_traits::Float;
+use tensor::{BackendProvider, Shape, Tensor};
+
+use crate::activations::{Parametric, Stateless};
+
+/// Swish [`Parametric<T>`] function with a trainable `beta`
+/// ```text
+/// pswish(x) = x / (1 + e^(-beta*x))
+/// ```
+pub struct PSwish<T, B: BackendProvider> {
+    beta: Tensor<T, B, 0>,
+}
+
+impl<T: Float, B: BackendProvider> PSwish<T, B> {
+    /// [`PSwish<T, B>`] constructor
+    pub fn new(beta: T) -> Self {
+        Self { beta: Tensor::new(beta, Shape::zero()) }
+    }
+}
+
+impl<T, B: BackendProvider> !Stateless for PSwish<T, B> {}
+
+impl<T: Float + From<i32>, B: BackendProvider> PSwish<T, B> {
+    fn sigmoid(&self, x: T) -> T {
+        T::from(1) / (T::from(1) + (-self.beta[0] * x).exp())
+    }
+}
+
+impl<T: Float + From<i32>, B: BackendProvider> Parametric<T> for PSwish<T, B> {
+    type Parameters = Tensor<T, B, 0>;
+
+    fn build(&mut self, _: usize) {}
+
+    fn activate_at(&self, _: usize, x: T) -> T {
+        x * self.sigmoid(x)
+    }
+
+    fn derive_at(&self, _: usize, x: T) -> T {
+        let s = self.sigmoid(x);
+        s + self.beta[0] * x * s * (T::from(1) - s)
+    }
+
+    fn derive_parameters<I: Iterator<Item=(usize, T, T)>>(&self, iter: I) -> Self::Parameters {
+        let beta_d = iter.map(|(_, x, d)| {
+            let s = self.sigmoid(x);
+            x * x * s * (T::from(1) - s) * d
+        }).sum::<T>();
+        Tensor::new(beta_d, Shape::zero())
+    }
+
+    fn update(&mut self, update: &Self::Parameters) {
+        self.beta[0] = self.beta[0] - update[0];
+    }
+}
@@ -0,0 +1,53 @@
+use std::marker::PhantomData;
+
+use num_traits::Float;
+
+use crate::activations::{Activation, ratio};
+
+/// Scaled Exponential Linear Unit [`Activation<T>`] function
+/// ```text
+///            LAMBDA*ALPHA*(e^x - 1) ; x <= 0
+/// selu(x) =  LAMBDA*x               ; x > 0
+/// ```
+/// Self-normalizes when the kernel is initialized with `VarianceScaling::lecun`.
+pub struct SELU<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> SELU<T> {
+    /// `ALPHA` as `(numerator, denominator)`, to nine decimal places
+    pub const ALPHA: (i32, i32) = (1673263242, 1000000000);
+    /// `LAMBDA` as `(numerator, denominator)`, to nine decimal places
+    pub const LAMBDA: (i32, i32) = (1050700987, 1000000000);
+
+    /// [`SELU<T>`] constructor
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<T> Default for SELU<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float + From<i32>> Activation<T> for SELU<T> {
+    fn activate(&self, x: T) -> T {
+        let lambda = ratio::<T>(Self::LAMBDA.0, Self::LAMBDA.1);
+        if x > T::zero() {
+            lambda * x
+        } else {
+            lambda * ratio(Self::ALPHA.0, Self::ALPHA.1) * (x.exp() - T::from(1))
+        }
+    }
+
+    fn derive(&self, x: T) -> T {
+        let lambda = ratio::<T>(Self::LAMBDA.0, Self::LAMBDA.1);
+        if x > T::zero() {
+            lambda
+        } else {
+            lambda * ratio(Self::ALPHA.0, Self::ALPHA.1) * x.exp()
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::{
+    cmp::Ordering,
+    marker::PhantomData,
+};
+
+use num_traits::Float;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    activations::{Elementwise, TensorActivation},
+    layers::fibers,
+};
+
+/// SoftMax [`TensorActivation<T, B, N>`] function over the trailing axis
+/// ```text
+/// softmax(x)[i] = e^x[i] / sum(e^x[j])
+/// ```
+pub struct SoftMax<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> SoftMax<T> {
+    /// [`SoftMax<T>`] constructor
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<T> Default for SoftMax<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> !Elementwise for SoftMax<T> {}
+
+/// Numerically stable softmax of one fiber of `z`, written into `output`
+pub(crate) fn softmax<T: Float, B: BackendProvider, const N: usize, I: Iterator<Item=usize> + Clone>(
+    z: &Tensor<T, B, N>,
+    fiber: I,
+    output: &mut Tensor<T, B, N>,
+) {
+    let max = fiber.clone()
+        .map(|i| z[i])
+        .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .unwrap_or(T::zero());
+    fiber.clone().for_each(|i| output[i] = (z[i] - max).exp());
+    let s = fiber.clone().map(|i| output[i]).sum::<T>();
+    fiber.for_each(|i| output[i] /= s);
+}
+
+impl<T: Float, B: BackendProvider, const N: usize> TensorActivation<T, B, N> for SoftMax<T> {
+    type Parameters = [Void; 0];
+    type State = Vec<T>;
+
+    fn pre_activation_shape(&mut self, output_shape: &Shape<N>) -> Shape<N> {
+        output_shape.clone()
+    }
+
+    fn forward(&self, z: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::State) {
+        let mut output = Tensor::new(T::zero(), z.shape().clone());
+        fibers(z.shape(), Some(N - 1)).for_each(|f| softmax(&z, f, &mut output));
+        let state = output.iter().copied().collect();
+        (output, state)
+    }
+
+    fn backward(&self, state: Self::State, d: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::Parameters) {
+        let mut z_d = Tensor::new(T::zero(), d.shape().clone());
+        fibers(d.shape(), Some(N - 1)).for_each(|f| {
+            let dot = f.clone().map(|i| state[i] * d[i]).sum::<T>();
+            f.for_each(|i| z_d[i] = state[i] * (d[i] - dot));
+        });
+        (z_d, [])
+    }
+
+    fn update(&mut self, []: &Self::Parameters) {}
+}
@@ -0,0 +1,102 @@
+use std::{
+    cmp::Ordering,
+    marker::PhantomData,
+};
+
+use num_traits::Float;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    activations::{Elementwise, TensorActivation},
+    layers::fibers,
+};
+
+/// SparseMax [`TensorActivation<T, B, N>`] function over the trailing axis
+///
+/// Euclidean projection onto the probability simplex, which unlike softmax can output exact zeros.
+/// ```text
+/// sparsemax(x)[i] = max(x[i] - tau(x), 0)
+/// ```
+pub struct SparseMax<T> {
+    _marker: PhantomData<T>,
+}
+
+impl<T> SparseMax<T> {
+    /// [`SparseMax<T>`] constructor
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<T> Default for SparseMax<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> !Elementwise for SparseMax<T> {}
+
+impl<T: Float + From<i32>, B: BackendProvider, const N: usize> TensorActivation<T, B, N> for SparseMax<T> {
+    type Parameters = [Void; 0];
+    type State = Vec<T>;
+
+    fn pre_activation_shape(&mut self, output_shape: &Shape<N>) -> Shape<N> {
+        output_shape.clone()
+    }
+
+    fn forward(&self, z: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::State) {
+        let mut output = Tensor::new(T::zero(), z.shape().clone());
+        fibers(z.shape(), Some(N - 1)).for_each(|f| {
+            let mut sorted = f.clone().map(|i| z[i]).collect::<Vec<_>>();
+            sorted.sort_by(|a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+            let mut sum = T::zero();
+            let mut tau = T::zero();
+            for (k, x) in sorted.into_iter().enumerate() {
+                let k = T::from(k as i32 + 1);
+                if T::from(1) + k * x <= sum + x {
+                    break;
+                }
+                sum += x;
+                tau = (sum - T::from(1)) / k;
+            }
+            f.for_each(|i| output[i] = if z[i] > tau { z[i] - tau } else { T::zero() });
+        });
+        let state = output.iter().copied().collect();
+        (output, state)
+    }
+
+    fn backward(&self, state: Self::State, d: Tensor<T, B, N>) -> (Tensor<T, B, N>, Self::Parameters) {
+        let mut z_d = Tensor::new(T::zero(), d.shape().clone());
+        fibers(d.shape(), Some(N - 1)).for_each(|f| {
+            let support = f.clone().filter(|&i| state[i] > T::zero());
+            let size = T::from(support.clone().count() as i32);
+            let mean = support.clone().map(|i| d[i]).sum::<T>() / size;
+            support.for_each(|i| z_d[i] = d[i] - mean);
+        });
+        (z_d, [])
+    }
+
+    fn update(&mut self, []: &Self::Parameters) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        activations::TensorActivation,
+        testing::{assert_close, check_activation_gradient, tensor, values},
+    };
+
+    use super::SparseMax;
+
+    #[test]
+    fn projects_onto_the_simplex() {
+        let (output, _) = SparseMax::new().forward(tensor([1, 3], &[0.5, 0.4, -1.]));
+        [0.55, 0.45, 0.].into_iter().enumerate().for_each(|(i, expected)| assert_close(output[i], expected));
+    }
+
+    #[test]
+    fn gradient() {
+        check_activation_gradient(&SparseMax::new(), tensor([3, 4], &values(12, 1)));
+    }
+}
@@ -80,3 +80,23 @@ impl<A: Package, B: Package> Package for (A, B) {
         (self.0.shape_refs(), self.1.shape_refs())
     }
 }
+
+/// [`Package`] whose leading axis is masked, `false` marking the entries that should be ignored
+pub struct Masked<P, B: BackendProvider> {
+    pub value: P,
+    pub mask: Tensor<bool, B, 1>,
+}
+
+/// Implemented for every type that is not a [`Masked`] package
+pub auto trait Unmasked {}
+
+impl<P, B: BackendProvider> ! Unmasked for Masked<P, B> {}
+
+impl<P: Package, B: BackendProvider> Package for Masked<P, B> {
+    const LEN: usize = P::LEN;
+    type Shapes = P::Shapes;
+
+    fn shape_refs(&self) -> <Self::Shapes as FromRef>::Ref<'_> {
+        self.value.shape_refs()
+    }
+}
@@ -9,4 +9,12 @@ pub trait Dataset {
     fn get_training_iter(&self) -> Self::Iter;
 
     fn get_testing_iter(&self) -> Self::Iter;
+
+    /// Weight in the loss of the `index`-th training example
+    ///
+    /// Takes the position in [`get_training_iter`](Dataset::get_training_iter) and the example itself,
+    /// so weights can differ between examples with the same label.
+    fn sample_weight(&self, _index: usize, _input: &Self::Input, _label: &Self::Label) -> f64 {
+        1.
+    }
 }
@@ -1,51 +1,42 @@
 use std::marker::PhantomData;
 
 use num_traits::Number;
-use tensor::{Shape, Tensor, VecProvider};
-use void::Void;
+use tensor::{BackendProvider, Shape, Tensor};
 
 use crate::{
-    activations::Activation as Act,
+    activations::TensorActivation,
     layers::{
         Layer,
-        LayerBuilder
+        LayerBuilder,
+        masked::MaskPreserving,
     },
     data::{Package, FromRef, Uninitialized}
 };
 
-pub struct Activation<T, A: Act<T>, const N: usize> {
+/// Applies a shape preserving [`TensorActivation<T, B, N>`] to its input
+pub struct Activation<T, B: BackendProvider, A: TensorActivation<T, B, N>, const N: usize> {
     activation: A,
     shape: Shape<N>,
-    _marker: PhantomData<T>,
+    _marker: PhantomData<(T, B)>,
 }
 
-impl<T: Copy, A: Act<T>, const N: usize> Activation<T, A, N> {
-    pub fn new(activation: A, shape: Shape<N>) -> Self {
+impl<T, B: BackendProvider, A: TensorActivation<T, B, N>, const N: usize> Activation<T, B, A, N> {
+    pub fn new(mut activation: A, shape: Shape<N>) -> Self {
+        assert_eq!(activation.pre_activation_shape(&shape), shape);
         Self {
             activation,
             shape,
             _marker: PhantomData,
         }
     }
-
-    fn feed_forward<F: FnMut(usize, T)>(&self, input: Tensor<T, VecProvider, N>, mut f: F) -> Tensor<T, VecProvider, N> {
-        assert_eq!(input.shape(), &self.shape);
-
-        let mut o = input;
-        o.iter_mut().enumerate().for_each(|(i, t)| {
-            f(i, self.activation.derive(*t));
-            *t = self.activation.activate(*t);
-        });
-        o
-    }
 }
 
-impl<T: Number, A: Act<T>, const N: usize> Layer for Activation<T, A, N> {
-    type Input = Tensor<T, VecProvider, N>;
-    type ReverseInput = Tensor<T, VecProvider, N>;
-    type Internal = [Tensor<Void, VecProvider, 0>; 0];
-    type Output = Tensor<T, VecProvider, N>;
-    type ReverseOutput = Tensor<T, VecProvider, N>;
+impl<T: Number, B: BackendProvider, A: TensorActivation<T, B, N>, const N: usize> Layer for Activation<T, B, A, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = A::Parameters;
+    type Output = Tensor<T, B, N>;
+    type ReverseOutput = Tensor<T, B, N>;
 
     type Computation<'s> = impl FnOnce(
         Self::ReverseOutput
@@ -66,7 +57,9 @@ impl<T: Number, A: Act<T>, const N: usize> Layer for Activation<T, A, N> {
         &self,
         input: Self::Input,
     ) -> Self::Output {
-        self.feed_forward(input, |_, _| {})
+        assert_eq!(input.shape(), &self.shape);
+
+        self.activation.forward(input).0
     }
 
     fn back_propagate(
@@ -76,31 +69,33 @@ impl<T: Number, A: Act<T>, const N: usize> Layer for Activation<T, A, N> {
         Self::Output,
         Self::Computation<'_>,
     ) {
-        let mut derivatives = Tensor::<_, VecProvider, _>::new(T::zero(), self.shape.clone());
+        assert_eq!(input.shape(), &self.shape);
+
+        let (output, state) = self.activation.forward(input);
         (
-            self.feed_forward(input, |i, t| derivatives[i] = t),
-            |output_d| {
+            output,
+            move |output_d| {
                 assert_eq!(output_d.shape(), &self.shape);
-                derivatives
-                    .iter_mut()
-                    .zip(output_d.iter())
-                    .for_each(|(a, b)| *a *= *b);
-                (derivatives, [])
+                self.activation.backward(state, output_d)
             }
         )
     }
 
-    fn update(&mut self, []: &Self::Internal) {}
+    fn update(&mut self, update: &Self::Internal) {
+        self.activation.update(update)
+    }
 }
 
+impl<T: Number, B: BackendProvider, A: TensorActivation<T, B, N>, const N: usize> MaskPreserving for Activation<T, B, A, N> {}
+
 builder::builder! {
-    pub struct Builder<const N: usize, (T)> {
+    pub struct Builder<const N: usize, (T), (B)> {
         activation: A,
     }
 }
 
-impl<T: Number, A: Act<T>, const N: usize> LayerBuilder for Builder<A, usizeContainer<N>, T> {
-    type Layer = Activation<T, A, N>;
+impl<T: Number, B: BackendProvider, A: TensorActivation<T, B, N>, const N: usize> LayerBuilder for Builder<A, usizeContainer<N>, T, B> {
+    type Layer = Activation<T, B, A, N>;
 
     fn build(self, input_shapes: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
         Self::Layer::new(self.activation, input_shapes.clone())
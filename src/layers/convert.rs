@@ -12,11 +12,48 @@ use crate::{
     layers::{
         Layer,
         LayerBuilder,
+        masked::MaskPreserving,
     },
 };
-use crate::data::{Package, Uninitialized};
+use crate::data::{Package, Initialized, Uninitialized};
 use crate::data::FromRef;
 
+/// Numeric conversion between element types with the semantics of an `as` cast
+pub trait Cast<U> {
+    fn cast(self) -> U;
+
+    /// Converts a derivative into `U`, which is zero when `U` is an integer type as it is not differentiable
+    fn cast_derivative(self) -> U;
+}
+
+macro_rules! impl_cast {
+    (@float $e:expr) => { $e };
+    (@integer $e:expr) => { 0 };
+    ($($t:ty => $($u:ty: $d:ident),+;)+) => {
+        $($(
+            impl Cast<$u> for $t {
+                fn cast(self) -> $u {
+                    self as $u
+                }
+
+                fn cast_derivative(self) -> $u {
+                    impl_cast!(@$d self as $u)
+                }
+            }
+        )+)+
+    };
+}
+
+impl_cast! {
+    u8 => u8: integer, i32: integer, f32: float, f64: float;
+    i32 => u8: integer, i32: integer, f32: float, f64: float;
+    f32 => u8: integer, i32: integer, f32: float, f64: float;
+    f64 => u8: integer, i32: integer, f32: float, f64: float;
+}
+
+/// Casts its input to `U` and applies an activation
+///
+/// Derivatives only flow back into float inputs, integer inputs receive zero derivatives.
 pub struct Convert<T, U, B, A, const N: usize> {
     shape: Shape<N>,
     activation: A,
@@ -33,18 +70,19 @@ impl<T, U, B: BackendProvider, A, const N: usize> Convert<T, U, B, A, N> {
     }
 }
 
-impl<T, U, B: BackendProvider, A: Activation<U>, const N: usize> Convert<T, U, B, A, N> {
+impl<T: Copy + Cast<U>, U: Number + Cast<T>, B: BackendProvider, A: Activation<U>, const N: usize> Convert<T, U, B, A, N> {
     fn feed_forward<F: FnMut(usize, U)>(&self, input: &Tensor<T, B, N>, mut f: F) -> Tensor<U, B, N> {
-        todo!()
-        // self.shape.clone().into_tensor(move |i| {
-        //     let t = input[i].into();
-        //     f(i, self.activation.derive(t));
-        //     self.activation.activate(t)
-        // })
+        assert_eq!(input.shape(), &self.shape);
+
+        self.shape.clone().into_tensor(move |i| {
+            let t = input[i].cast();
+            f(i, self.activation.derive(t));
+            self.activation.activate(t)
+        })
     }
 }
 
-impl<T, U, B: BackendProvider, A: Activation<U>, const N: usize> Layer for Convert<T, U, B, A, N> {
+impl<T: Copy + Cast<U>, U: Number + Cast<T>, B: BackendProvider, A: Activation<U>, const N: usize> Layer for Convert<T, U, B, A, N> {
     type Input = Tensor<T, B, N>;
     type ReverseInput = Tensor<T, B, N>;
     type Internal = [Void; 0];
@@ -71,27 +109,28 @@ impl<T, U, B: BackendProvider, A: Activation<U>, const N: usize> Layer for Conve
     }
 
     fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
-        (self.feed_forward(&input, |_, _| {}), |output_d| {
-            assert_eq!(output_d.shape(), &self.shape);
-            (input, [])
-        })
-        // let mut derivatives = Tensor::<_, B, _>::new(T::zero(), self.shape.clone());
-        // (
-        //     self.feed_forward(&input, |i, t| derivatives[i] = t.into()),
-        //     move |output_d| (self.shape.clone().into_tensor(|i| (output_d[i] * derivatives[i].into()).into()), []),
-        // )
+        let mut derivatives = Vec::with_capacity(self.shape.capacity());
+        (
+            self.feed_forward(&input, |_, d| derivatives.push(d)),
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.shape);
+                (self.shape.clone().into_tensor(|i| (output_d[i] * derivatives[i]).cast_derivative()), [])
+            },
+        )
     }
 
     fn update(&mut self, []: &Self::Internal) {}
 }
 
+impl<T: Copy + Cast<U>, U: Number + Cast<T>, B: BackendProvider, A: Activation<U>, const N: usize> MaskPreserving for Convert<T, U, B, A, N> {}
+
 builder::builder! {
     pub struct Builder<(T), (U), (B), const N: usize> {
         activation: A,
     }
 }
 
-impl<T, U, B: BackendProvider, A: Activation<U>, const N: usize> LayerBuilder for Builder<A, T, U, B, usizeContainer<N>> {
+impl<T: Copy + Cast<U>, U: Number + Cast<T>, B: BackendProvider, A: Activation<U> + Initialized, const N: usize> LayerBuilder for Builder<A, T, U, B, usizeContainer<N>> {
     type Layer = Convert<T, U, B, A, N>;
 
     fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
@@ -99,10 +138,15 @@ impl<T, U, B: BackendProvider, A: Activation<U>, const N: usize> LayerBuilder fo
     }
 }
 
-// impl<T, U, B: BackendProvider, const N: usize> LayerBuilder for Builder<Uninitialized, T, U, B, usizeContainer<N>> {
-//     type Layer = Convert<T, U, B, Identity<U>, N>;
-//
-//     fn build(self, input_shape: <<Self::Layer as Layer>::InputType as Package>::Shapes) -> Self::Layer {
-//         Self::Layer::new(input_shape, Identity::new())
-//     }
-// }
+impl<
+    T: Copy + Cast<U>,
+    U: Float + From<i32> + Cast<T>,
+    B: BackendProvider,
+    const N: usize,
+> LayerBuilder for Builder<Uninitialized, T, U, B, usizeContainer<N>> {
+    type Layer = Convert<T, U, B, Identity<U>, N>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(input_shape, Identity::new())
+    }
+}
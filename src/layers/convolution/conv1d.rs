@@ -3,7 +3,7 @@ use num_traits::Number;
 use tensor::{BackendProvider, Shape, SliceIndex, Tensor};
 
 use crate::{
-    activations::Activation,
+    activations::{self, Parametric},
     constraints::Constraint,
     initializers::Initializer,
     regularizers::Regularizer,
@@ -17,7 +17,7 @@ use crate::{
 pub struct Conv1D<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -42,7 +42,7 @@ pub struct Conv1D<
 impl<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -55,7 +55,7 @@ impl<
         kernel_shape: [usize; 1],
         mut kernel_initializer: KI,
         mut bias_initializer: BI,
-        activation: A,
+        mut activation: A,
         padding: [Padding; 1],
         strides: [usize; 1],
         dilation: [usize; 1],
@@ -68,11 +68,12 @@ impl<
     ) -> Self {
         assert_eq!(filters % groups, 0);
         assert_eq!(input_shape[1] % groups, 0);
-        let padding = padding.map(Padding::resolve);
+        let padding = [padding[0].resolve(input_shape[0], kernel_shape[0], strides[0], dilation[0])];
         let output_shape = Shape::new([
             (input_shape[0] + padding[0].0 + padding[0].1 - dilation[0] * (kernel_shape[0] - 1) - 1) / strides[0] + 1,
             filters,
         ]);
+        activation.build(filters);
         Self {
             kernel: kernel_initializer.initialize([kernel_shape[0], input_shape[1] / groups, filters].into()),
             bias: bias_initializer.initialize(output_shape.clone()),
@@ -113,7 +114,7 @@ impl<
         self.iter_through_output(|[o0, o1]| {
             let mut o = self.bias[[o0, o1]];
             self.iter_through_kernel(|[k0, k1]| {
-                let i0 = (o0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
+                let i0 = (o0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
                 if let Some(i0) = i0 {
                     let groups = self.input_shape[1] / self.kernel.shape()[1];
                     for g in (0..groups).map(|g| g * self.kernel.shape()[1]) {
@@ -121,8 +122,8 @@ impl<
                     }
                 }
             });
-            f([o0, o1], self.activation.derive(o));
-            o = self.activation.activate(o);
+            f([o0, o1], o);
+            o = self.activation.activate_at(o1, o);
             output[[o0, o1]] = o
         });
         output
@@ -130,9 +131,9 @@ impl<
 }
 
 impl<
-    T: Number + for<'s> Add<&'s T, Output=T>,
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR: Regularizer<T, B, 3>,
     BR: Regularizer<T, B, 2>,
     AR: Regularizer<T, B, 2>,
@@ -141,7 +142,7 @@ impl<
 > Layer for Conv1D<T, B, A, KR, BR, AR, KC, BC> {
     type Input = Tensor<T, B, 2>;
     type ReverseInput = Tensor<T, B, 2>;
-    type Internal = (Tensor<T, B, 3>, Tensor<T, B, 2>);
+    type Internal = ((Tensor<T, B, 3>, Tensor<T, B, 2>), A::Parameters);
     type Output = Tensor<T, B, 2>;
     type ReverseOutput = Tensor<T, B, 2>;
 
@@ -181,13 +182,17 @@ impl<
             output,
             move |output_d| {
                 assert_eq!(output_d.shape(), &self.output_shape);
+                let activation_d = activations::back_propagate(
+                    &self.activation,
+                    &mut derivatives,
+                    |o| output_d[o] + activation_reg[o],
+                );
                 let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
                 let mut kernel_d = self.kernel_regularizer.derive(&self.kernel);
                 let bias_d = self.bias_regularizer.derive(&self.bias) + derivatives.slice(SliceIndex::full()).unwrap();
                 self.iter_through_output(|[o0, o1]| {
-                    derivatives[[o0, o1]] *= output_d[[o0, o1]] + activation_reg[[o0, o1]];
                     self.iter_through_kernel(|[k0, k1]| {
-                        let i0 = (o0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
+                        let i0 = (o0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
                         if let Some(i0) = i0 {
                             let groups = self.input_shape[1] / self.kernel.shape()[1];
                             for g in (0..groups).map(|g| g * self.kernel.shape()[1]) {
@@ -197,12 +202,12 @@ impl<
                         }
                     })
                 });
-                (input_d, (kernel_d, bias_d))
+                (input_d, ((kernel_d, bias_d), activation_d))
             }
         )
     }
 
-    fn update(&mut self, (kernel, bias): &Self::Internal) {
+    fn update(&mut self, ((kernel, bias), activation): &Self::Internal) {
         self.kernel
             .iter_mut()
             .zip(kernel.iter().copied())
@@ -211,13 +216,20 @@ impl<
             .iter_mut()
             .zip(bias.iter().copied())
             .for_each(|(a, b)| *a = self.bias_constraint.constrain(*a - b));
+        self.activation.update(activation);
+    }
+
+    fn regularization(&self) -> f64 {
+        let kernel: f64 = self.kernel_regularizer.regularization(&self.kernel).into();
+        let bias: f64 = self.bias_regularizer.regularization(&self.bias).into();
+        kernel + bias
     }
 }
 
 impl<
-    T: Number + for<'s> Add<&'s T, Output=T>,
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     P: IntoPadding<1>,
     S: IntoStride<1>,
     D: IntoDilation<1>,
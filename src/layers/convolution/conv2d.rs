@@ -3,7 +3,7 @@ use num_traits::Number;
 use tensor::{BackendProvider, Shape, SliceIndex, Tensor};
 
 use crate::{
-    activations::Activation,
+    activations::{self, Parametric},
     constraints::Constraint,
     initializers::Initializer,
     regularizers::Regularizer,
@@ -17,7 +17,7 @@ use crate::{
 pub struct Conv2D<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -42,7 +42,7 @@ pub struct Conv2D<
 impl<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -55,7 +55,7 @@ impl<
         kernel_shape: [usize; 2],
         mut kernel_initializer: KI,
         mut bias_initializer: BI,
-        activation: A,
+        mut activation: A,
         padding: [Padding; 2],
         strides: [usize; 2],
         dilation: [usize; 2],
@@ -68,12 +68,16 @@ impl<
     ) -> Self {
         assert_eq!(filters % groups, 0);
         assert_eq!(input_shape[2] % groups, 0);
-        let padding = padding.map(Padding::resolve);
+        let padding = [
+            padding[0].resolve(input_shape[0], kernel_shape[0], strides[0], dilation[0]),
+            padding[1].resolve(input_shape[1], kernel_shape[1], strides[1], dilation[1]),
+        ];
         let output_shape = Shape::new([
             (input_shape[0] + padding[0].0 + padding[0].1 - dilation[0] * (kernel_shape[0] - 1) - 1) / strides[0] + 1,
             (input_shape[1] + padding[1].0 + padding[1].1 - dilation[1] * (kernel_shape[1] - 1) - 1) / strides[1] + 1,
             filters,
         ]);
+        activation.build(filters);
         Self {
             kernel: kernel_initializer.initialize([kernel_shape[0], kernel_shape[1], input_shape[2] / groups, filters].into()),
             bias: bias_initializer.initialize(output_shape.clone()),
@@ -118,8 +122,8 @@ impl<
         self.iter_through_output(|[o0, o1, o2]| {
             let mut o = self.bias[[o0, o1, o2]];
             self.iter_through_kernel(|[k0, k1, k2]| {
-                let i0 = (o0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
-                let i1 = (o1 * self.dilation[1] + k1 * self.strides[1]).checked_sub(self.padding[1].0);
+                let i0 = (o0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
+                let i1 = (o1 * self.strides[1] + k1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&i1| i1 < self.input_shape[1]);
                 if let (Some(i0), Some(i1)) = (i0, i1) {
                     let groups = self.input_shape[2] / self.kernel.shape()[2];
                     for g in (0..groups).map(|g| g * self.kernel.shape()[2]) {
@@ -127,8 +131,8 @@ impl<
                     }
                 }
             });
-            f([o0, o1, o2], self.activation.derive(o));
-            o = self.activation.activate(o);
+            f([o0, o1, o2], o);
+            o = self.activation.activate_at(o2, o);
             output[[o0, o1, o2]] = o
         });
         output
@@ -136,9 +140,9 @@ impl<
 }
 
 impl<
-    T: Number + for<'s> Add<&'s T, Output=T>,
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR: Regularizer<T, B, 4>,
     BR: Regularizer<T, B, 3>,
     AR: Regularizer<T, B, 3>,
@@ -147,7 +151,7 @@ impl<
 > Layer for Conv2D<T, B, A, KR, BR, AR, KC, BC> {
     type Input = Tensor<T, B, 3>;
     type ReverseInput = Tensor<T, B, 3>;
-    type Internal = (Tensor<T, B, 4>, Tensor<T, B, 3>);
+    type Internal = ((Tensor<T, B, 4>, Tensor<T, B, 3>), A::Parameters);
     type Output = Tensor<T, B, 3>;
     type ReverseOutput = Tensor<T, B, 3>;
 
@@ -187,14 +191,18 @@ impl<
             output,
             move |output_d| {
                 assert_eq!(output_d.shape(), &self.output_shape);
+                let activation_d = activations::back_propagate(
+                    &self.activation,
+                    &mut derivatives,
+                    |o| output_d[o] + activation_reg[o],
+                );
                 let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
                 let mut kernel_d = self.kernel_regularizer.derive(&self.kernel);
                 let bias_d = self.bias_regularizer.derive(&self.bias) + derivatives.slice(SliceIndex::full()).unwrap();
                 self.iter_through_output(|[o0, o1, o2]| {
-                    derivatives[[o0, o1, o2]] *= output_d[[o0, o1, o2]] + activation_reg[[o0, o1, o2]];
                     self.iter_through_kernel(|[k0, k1, k2]| {
-                        let i0 = (o0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
-                        let i1 = (o1 * self.dilation[1] + k1 * self.strides[1]).checked_sub(self.padding[1].0);
+                        let i0 = (o0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
+                        let i1 = (o1 * self.strides[1] + k1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&i1| i1 < self.input_shape[1]);
                         if let (Some(i0), Some(i1)) = (i0, i1) {
                             let groups = self.input_shape[2] / self.kernel.shape()[2];
                             for g in (0..groups).map(|g| g * self.kernel.shape()[2]) {
@@ -204,12 +212,12 @@ impl<
                         }
                     })
                 });
-                (input_d, (kernel_d, bias_d))
+                (input_d, ((kernel_d, bias_d), activation_d))
             },
         )
     }
 
-    fn update(&mut self, (kernel, bias): &Self::Internal) {
+    fn update(&mut self, ((kernel, bias), activation): &Self::Internal) {
         self.kernel
             .iter_mut()
             .zip(kernel.iter().copied())
@@ -218,13 +226,20 @@ impl<
             .iter_mut()
             .zip(bias.iter().copied())
             .for_each(|(a, b)| *a = self.bias_constraint.constrain(*a - b));
+        self.activation.update(activation);
+    }
+
+    fn regularization(&self) -> f64 {
+        let kernel: f64 = self.kernel_regularizer.regularization(&self.kernel).into();
+        let bias: f64 = self.bias_regularizer.regularization(&self.bias).into();
+        kernel + bias
     }
 }
 
 impl<
-    T: Number + for<'s> Add<&'s T, Output=T>,
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     P: IntoPadding<2>,
     S: IntoStride<2>,
     D: IntoDilation<2>,
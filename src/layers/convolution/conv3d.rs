@@ -3,7 +3,7 @@ use num_traits::Number;
 use tensor::{BackendProvider, Shape, SliceIndex, Tensor};
 
 use crate::{
-    activations::Activation,
+    activations::{self, Parametric},
     constraints::Constraint,
     initializers::Initializer,
     regularizers::Regularizer,
@@ -17,7 +17,7 @@ use crate::{
 pub struct Conv3D<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -42,7 +42,7 @@ pub struct Conv3D<
 impl<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -55,7 +55,7 @@ impl<
         kernel_shape: [usize; 3],
         mut kernel_initializer: KI,
         mut bias_initializer: BI,
-        activation: A,
+        mut activation: A,
         padding: [Padding; 3],
         strides: [usize; 3],
         dilation: [usize; 3],
@@ -68,13 +68,18 @@ impl<
     ) -> Self {
         assert_eq!(filters % groups, 0);
         assert_eq!(input_shape[3] % groups, 0);
-        let padding = padding.map(Padding::resolve);
+        let padding = [
+            padding[0].resolve(input_shape[0], kernel_shape[0], strides[0], dilation[0]),
+            padding[1].resolve(input_shape[1], kernel_shape[1], strides[1], dilation[1]),
+            padding[2].resolve(input_shape[2], kernel_shape[2], strides[2], dilation[2]),
+        ];
         let output_shape = Shape::new([
             (input_shape[0] + padding[0].0 + padding[0].1 - dilation[0] * (kernel_shape[0] - 1) - 1) / strides[0] + 1,
             (input_shape[1] + padding[1].0 + padding[1].1 - dilation[1] * (kernel_shape[1] - 1) - 1) / strides[1] + 1,
             (input_shape[2] + padding[2].0 + padding[2].1 - dilation[2] * (kernel_shape[2] - 1) - 1) / strides[2] + 1,
             filters,
         ]);
+        activation.build(filters);
         Self {
             kernel: kernel_initializer.initialize([kernel_shape[0], kernel_shape[1], kernel_shape[2], input_shape[3] / groups, filters].into()),
             bias: bias_initializer.initialize(output_shape.clone()),
@@ -123,9 +128,9 @@ impl<
         self.iter_through_output(|[o0, o1, o2, o3]| {
             let mut o = self.bias[[o0, o1, o2, o3]];
             self.iter_through_kernel(|[k0, k1, k2, k3]| {
-                let i0 = (o0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
-                let i1 = (o1 * self.dilation[1] + k1 * self.strides[1]).checked_sub(self.padding[1].0);
-                let i2 = (o2 * self.dilation[2] + k2 * self.strides[2]).checked_sub(self.padding[2].0);
+                let i0 = (o0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
+                let i1 = (o1 * self.strides[1] + k1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&i1| i1 < self.input_shape[1]);
+                let i2 = (o2 * self.strides[2] + k2 * self.dilation[2]).checked_sub(self.padding[2].0).filter(|&i2| i2 < self.input_shape[2]);
                 if let (Some(i0), Some(i1), Some(i2)) = (i0, i1, i2) {
                     let groups = self.input_shape[3] / self.kernel.shape()[3];
                     for g in (0..groups).map(|g| g * self.kernel.shape()[3]) {
@@ -133,8 +138,8 @@ impl<
                     }
                 }
             });
-            f([o0, o1, o2, o3], self.activation.derive(o));
-            o = self.activation.activate(o);
+            f([o0, o1, o2, o3], o);
+            o = self.activation.activate_at(o3, o);
             output[[o0, o1, o2, o3]] = o
         });
         output
@@ -142,9 +147,9 @@ impl<
 }
 
 impl<
-    T: Number + for<'s> Add<&'s T, Output=T>,
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR: Regularizer<T, B, 5>,
     BR: Regularizer<T, B, 4>,
     AR: Regularizer<T, B, 4>,
@@ -153,7 +158,7 @@ impl<
 > Layer for Conv3D<T, B, A, KR, BR, AR, KC, BC> {
     type Input = Tensor<T, B, 4>;
     type ReverseInput = Tensor<T, B, 4>;
-    type Internal = (Tensor<T, B, 5>, Tensor<T, B, 4>);
+    type Internal = ((Tensor<T, B, 5>, Tensor<T, B, 4>), A::Parameters);
     type Output = Tensor<T, B, 4>;
     type ReverseOutput = Tensor<T, B, 4>;
 
@@ -193,15 +198,19 @@ impl<
             output,
             move |output_d| {
                 assert_eq!(output_d.shape(), &self.output_shape);
+                let activation_d = activations::back_propagate(
+                    &self.activation,
+                    &mut derivatives,
+                    |o| output_d[o] + activation_reg[o],
+                );
                 let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
                 let mut kernel_d = self.kernel_regularizer.derive(&self.kernel);
                 let bias_d = self.bias_regularizer.derive(&self.bias) + derivatives.slice(SliceIndex::full()).unwrap();
                 self.iter_through_output(|[o0, o1, o2, o3]| {
-                    derivatives[[o0, o1, o2, o3]] *= output_d[[o0, o1, o2, o3]] + activation_reg[[o0, o1, o2, o3]];
                     self.iter_through_kernel(|[k0, k1, k2, k3]| {
-                        let i0 = (o0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
-                        let i1 = (o1 * self.dilation[1] + k1 * self.strides[1]).checked_sub(self.padding[1].0);
-                        let i2 = (o2 * self.dilation[2] + k2 * self.strides[2]).checked_sub(self.padding[2].0);
+                        let i0 = (o0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
+                        let i1 = (o1 * self.strides[1] + k1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&i1| i1 < self.input_shape[1]);
+                        let i2 = (o2 * self.strides[2] + k2 * self.dilation[2]).checked_sub(self.padding[2].0).filter(|&i2| i2 < self.input_shape[2]);
                         if let (Some(i0), Some(i1), Some(i2)) = (i0, i1, i2) {
                             let groups = self.input_shape[3] / self.kernel.shape()[3];
                             for g in (0..groups).map(|g| g * self.kernel.shape()[3]) {
@@ -212,12 +221,12 @@ impl<
                     })
                 });
 
-                (input_d, (kernel_d, bias_d))
+                (input_d, ((kernel_d, bias_d), activation_d))
             },
         )
     }
 
-    fn update(&mut self, (kernel, bias): &Self::Internal) {
+    fn update(&mut self, ((kernel, bias), activation): &Self::Internal) {
         self.kernel
             .iter_mut()
             .zip(kernel.iter().copied())
@@ -226,13 +235,20 @@ impl<
             .iter_mut()
             .zip(bias.iter().copied())
             .for_each(|(a, b)| *a = self.bias_constraint.constrain(*a - b));
+        self.activation.update(activation);
+    }
+
+    fn regularization(&self) -> f64 {
+        let kernel: f64 = self.kernel_regularizer.regularization(&self.kernel).into();
+        let bias: f64 = self.bias_regularizer.regularization(&self.bias).into();
+        kernel + bias
     }
 }
 
 impl<
-    T: Number + for<'s> Add<&'s T, Output=T>,
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     P: IntoPadding<3>,
     S: IntoStride<3>,
     D: IntoDilation<3>,
@@ -1,3 +1,5 @@
+use std::array;
+
 use crate::{
     constraints::IntoConstraint,
     data::Uninitialized,
@@ -16,18 +18,51 @@ pub enum Padding {
     None,
     Symmetrical(usize),
     Asymmetrical(usize, usize),
+    /// Pads so that the output size is `ceil(input / stride)`
+    Same,
+    /// Pads only the start of the axis so that an output never depends on later inputs
+    Causal,
 }
 
 impl Padding {
-    pub const fn resolve(self) -> (usize, usize) {
+    /// Resolves the padding before and after an axis of a convolution or pooling layer
+    pub const fn resolve(self, input: usize, kernel: usize, stride: usize, dilation: usize) -> (usize, usize) {
+        let kernel = dilation * (kernel - 1) + 1;
+        match self {
+            Padding::None => (0, 0),
+            Padding::Symmetrical(p) => (p, p),
+            Padding::Asymmetrical(p0, p1) => (p0, p1),
+            Padding::Same => {
+                let output = (input + stride - 1) / stride;
+                let total = ((output - 1) * stride + kernel).saturating_sub(input);
+                (total / 2, total - total / 2)
+            }
+            Padding::Causal => (kernel - 1, 0),
+        }
+    }
+
+    /// Resolves the cropping before and after an axis of a deconvolution layer
+    pub const fn resolve_transposed(self, input: usize, kernel: usize, stride: usize, dilation: usize) -> (usize, usize) {
+        let kernel = dilation * (kernel - 1) + 1;
         match self {
             Padding::None => (0, 0),
             Padding::Symmetrical(p) => (p, p),
             Padding::Asymmetrical(p0, p1) => (p0, p1),
+            Padding::Same => {
+                // full output is `(input - 1) * stride + kernel`, `Same` keeps `input * stride` of it
+                let total = kernel.saturating_sub(stride);
+                (total / 2, total - total / 2)
+            }
+            Padding::Causal => (0, kernel.saturating_sub(stride)),
         }
     }
 }
 
+/// Resolves the padding of every axis of a layer without a kernel, such as zero padding or cropping
+pub(crate) fn resolve_unit_kernel<const N: usize>(padding: [Padding; N], input_shape: &[usize]) -> [(usize, usize); N] {
+    array::from_fn(|i| padding[i].resolve(input_shape[i], 1, 1, 1))
+}
+
 pub trait IntoPadding<const N: usize> {
     fn into_padding(self) -> [Padding; N];
 }
@@ -0,0 +1,136 @@
+use std::{
+    array,
+    marker::PhantomData,
+};
+
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    data::{Package, FromRef, Uninitialized},
+    layers::{
+        convolution::{resolve_unit_kernel, IntoPadding},
+        iter_through_shape,
+        Layer,
+        LayerBuilder,
+    },
+};
+
+/// Removes entries from the start and end of the spatial axes of a channel-last rank `N` input
+pub struct Cropping<T, B, const N: usize> {
+    input_shape: Shape<N>,
+    output_shape: Shape<N>,
+    cropping: [(usize, usize); N],
+    _marker: PhantomData<(T, B)>,
+}
+
+pub type Cropping1D<T, B> = Cropping<T, B, 2>;
+pub type Cropping2D<T, B> = Cropping<T, B, 3>;
+pub type Cropping3D<T, B> = Cropping<T, B, 4>;
+
+impl<T: Number, B: BackendProvider, const N: usize> Cropping<T, B, N> {
+    pub(crate) fn new(input_shape: Shape<N>, cropping: [(usize, usize); N]) -> Self {
+        assert!((0..N).all(|i| cropping[i].0 + cropping[i].1 < input_shape[i]));
+        Self {
+            output_shape: Shape::new(array::from_fn(|i| input_shape[i] - cropping[i].0 - cropping[i].1)),
+            input_shape,
+            cropping,
+            _marker: PhantomData,
+        }
+    }
+
+    fn input_index(&self, oi: [usize; N]) -> [usize; N] {
+        array::from_fn(|i| oi[i] + self.cropping[i].0)
+    }
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> Layer for Cropping<T, B, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Tensor<T, B, N>;
+    type ReverseOutput = Tensor<T, B, N>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let mut output = Tensor::new(T::zero(), self.output_shape.clone());
+        iter_through_shape(&self.output_shape, |oi| output[oi] = input[self.input_index(oi)]);
+        output
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        (
+            self.feed_forward(input),
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
+                iter_through_shape(&self.output_shape, |oi| input_d[self.input_index(oi)] = output_d[oi]);
+                (input_d, [])
+            }
+        )
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize> {
+        cropping: C,
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    C: IntoPadding<1>,
+> LayerBuilder for Builder<C, T, B, usizeContainer<1>> {
+    type Layer = Cropping1D<T, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let [c0] = resolve_unit_kernel(self.cropping.into_padding(), &[input_shape[0]]);
+        Self::Layer::new(input_shape, [c0, (0, 0)])
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    C: IntoPadding<2>,
+> LayerBuilder for Builder<C, T, B, usizeContainer<2>> {
+    type Layer = Cropping2D<T, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let [c0, c1] = resolve_unit_kernel(self.cropping.into_padding(), &[input_shape[0], input_shape[1]]);
+        Self::Layer::new(input_shape, [c0, c1, (0, 0)])
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    C: IntoPadding<3>,
+> LayerBuilder for Builder<C, T, B, usizeContainer<3>> {
+    type Layer = Cropping3D<T, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let [c0, c1, c2] = resolve_unit_kernel(self.cropping.into_padding(), &[input_shape[0], input_shape[1], input_shape[2]]);
+        Self::Layer::new(input_shape, [c0, c1, c2, (0, 0)])
+    }
+}
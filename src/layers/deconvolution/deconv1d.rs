@@ -2,7 +2,7 @@ use num_traits::Number;
 use tensor::{BackendProvider, Shape, SliceIndex, Tensor};
 
 use crate::{
-    activations::Activation,
+    activations::{self, Parametric},
     constraints::Constraint,
     initializers::Initializer,
     regularizers::Regularizer,
@@ -19,7 +19,7 @@ use crate::{
 pub struct Deconv1D<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -44,7 +44,7 @@ pub struct Deconv1D<
 impl<
     T: Number,
     B: BackendProvider<Backend<T>: Clone>,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -57,7 +57,7 @@ impl<
         kernel_shape: [usize; 1],
         mut kernel_initializer: KI,
         mut bias_initializer: BI,
-        activation: A,
+        mut activation: A,
         padding: [Padding; 1],
         strides: [usize; 1],
         dilation: [usize; 1],
@@ -67,11 +67,12 @@ impl<
         kernel_constraint: KC,
         bias_constraint: BC,
     ) -> Self {
-        let padding = padding.map(Padding::resolve);
+        let padding = [padding[0].resolve_transposed(input_shape[0], kernel_shape[0], strides[0], dilation[0])];
         let output_shape = Shape::new([
             (input_shape[0] - 1) * strides[0] + dilation[0] * (kernel_shape[0] - 1) + 1 - padding[0].0 - padding[0].1,
             filters,
         ]);
+        activation.build(filters);
         Self {
             kernel: kernel_initializer.initialize([kernel_shape[0], input_shape[1], filters].into()),
             bias: bias_initializer.initialize(output_shape.clone()),
@@ -111,7 +112,7 @@ impl<
         let mut output = self.bias.clone();
         self.iter_through_input(|[i0, i1]| {
             self.iter_through_kernel(|[k0, k2]| {
-                let o0 = (i0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
+                let o0 = (i0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&o0| o0 < self.output_shape[0]);
                 if let Some(o0) = o0 {
                     output[[o0, k2]] += input[[i0, i1]] * self.kernel[[k0, i1, k2]]
                 }
@@ -119,16 +120,16 @@ impl<
         });
         output.iter_mut_indexed().for_each(|(oi, t)| {
             f(oi, *t);
-            *t = self.activation.activate(*t)
+            *t = self.activation.activate_at(oi[1], *t)
         });
         output
     }
 }
 
 impl<
-    T: Number,
+    T: Number + Into<f64>,
     B: BackendProvider<Backend<T>: Clone>,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR: Regularizer<T, B, 3>,
     BR: Regularizer<T, B, 2>,
     AR: Regularizer<T, B, 2>,
@@ -137,7 +138,7 @@ impl<
 > Layer for Deconv1D<T, B, A, KR, BR, AR, KC, BC> {
     type Input = Tensor<T, B, 2>;
     type ReverseInput = Tensor<T, B, 2>;
-    type Internal = (Tensor<T, B, 3>, Tensor<T, B, 2>);
+    type Internal = ((Tensor<T, B, 3>, Tensor<T, B, 2>), A::Parameters);
     type Output = Tensor<T, B, 2>;
     type ReverseOutput = Tensor<T, B, 2>;
 
@@ -177,14 +178,18 @@ impl<
             output,
             move |output_d| {
                 assert_eq!(output_d.shape(), &self.output_shape);
+                let activation_d = activations::back_propagate(
+                    &self.activation,
+                    &mut derivatives,
+                    |o| output_d[o] + activation_reg[o],
+                );
                 let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
                 let mut kernel_d = self.kernel_regularizer.derive(&self.kernel);
                 let bias_d = self.bias_regularizer.derive(&self.bias);// todo: + derivatives.slice(SliceIndex::full()).unwrap();
-                derivatives.iter_mut().zip(output_d.iter().copied().zip(activation_reg.iter().copied())).for_each(|(a, (b, c))| *a *= b + c);
                 self.iter_through_input(|[i0, i1]| {
                     let mut i = T::zero();
                     self.iter_through_kernel(|[k0, k2]| {
-                        let o0 = (i0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
+                        let o0 = (i0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&o0| o0 < self.output_shape[0]);
                         if let Some(o0) = o0 {
                             i += self.kernel[[k0, i1, k2]] * derivatives[[o0, k2]];
                             kernel_d[[k0, i1, k2]] += input[[i0, i1]] * derivatives[[o0, k2]];
@@ -193,12 +198,12 @@ impl<
                     input_d[[i0, i1]] = i;
                 });
 
-                (input_d, (kernel_d, bias_d))
+                (input_d, ((kernel_d, bias_d), activation_d))
             },
         )
     }
 
-    fn update(&mut self, (kernel, bias): &Self::Internal) {
+    fn update(&mut self, ((kernel, bias), activation): &Self::Internal) {
         self.kernel
             .iter_mut()
             .zip(kernel.iter().copied())
@@ -207,5 +212,12 @@ impl<
             .iter_mut()
             .zip(bias.iter().copied())
             .for_each(|(a, b)| *a = self.bias_constraint.constrain(*a - b));
+        self.activation.update(activation);
+    }
+
+    fn regularization(&self) -> f64 {
+        let kernel: f64 = self.kernel_regularizer.regularization(&self.kernel).into();
+        let bias: f64 = self.bias_regularizer.regularization(&self.bias).into();
+        kernel + bias
     }
 }
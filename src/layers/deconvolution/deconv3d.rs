@@ -2,7 +2,7 @@ use num_traits::Number;
 use tensor::{BackendProvider, Shape, Tensor};
 
 use crate::{
-    activations::Activation,
+    activations::{self, Parametric},
     constraints::Constraint,
     initializers::Initializer,
     layers::{
@@ -17,7 +17,7 @@ use crate::data::FromRef;
 pub struct Deconv3D<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -42,7 +42,7 @@ pub struct Deconv3D<
 impl<
     T: Number,
     B: BackendProvider<Backend<T>: Clone>,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR,
     BR,
     AR,
@@ -55,7 +55,7 @@ impl<
         kernel_shape: [usize; 3],
         mut kernel_initializer: KI,
         mut bias_initializer: BI,
-        activation: A,
+        mut activation: A,
         padding: [Padding; 3],
         strides: [usize; 3],
         dilation: [usize; 3],
@@ -65,13 +65,18 @@ impl<
         kernel_constraint: KC,
         bias_constraint: BC,
     ) -> Self {
-        let padding = padding.map(Padding::resolve);
+        let padding = [
+            padding[0].resolve_transposed(input_shape[0], kernel_shape[0], strides[0], dilation[0]),
+            padding[1].resolve_transposed(input_shape[1], kernel_shape[1], strides[1], dilation[1]),
+            padding[2].resolve_transposed(input_shape[2], kernel_shape[2], strides[2], dilation[2]),
+        ];
         let output_shape = Shape::new([
             (input_shape[0] - 1) * strides[0] + dilation[0] * (kernel_shape[0] - 1) + 1 - padding[0].0 - padding[0].1,
             (input_shape[1] - 1) * strides[1] + dilation[1] * (kernel_shape[1] - 1) + 1 - padding[1].0 - padding[1].1,
             (input_shape[2] - 1) * strides[2] + dilation[2] * (kernel_shape[2] - 1) + 1 - padding[2].0 - padding[2].1,
             filters,
         ]);
+        activation.build(filters);
         Self {
             kernel: kernel_initializer.initialize([kernel_shape[0], kernel_shape[1], kernel_shape[2], input_shape[3], filters].into()),
             bias: bias_initializer.initialize(output_shape.clone()),
@@ -119,9 +124,9 @@ impl<
         let mut output = self.bias.clone();
         self.iter_through_input(|[i0, i1, i2, i3]| {
             self.iter_through_kernel(|[k0, k1, k2, k4]| {
-                let o0 = (i0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
-                let o1 = (i1 * self.dilation[1] + k1 * self.strides[1]).checked_sub(self.padding[1].0);
-                let o2 = (i2 * self.dilation[2] + k2 * self.strides[2]).checked_sub(self.padding[2].0);
+                let o0 = (i0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&o0| o0 < self.output_shape[0]);
+                let o1 = (i1 * self.strides[1] + k1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&o1| o1 < self.output_shape[1]);
+                let o2 = (i2 * self.strides[2] + k2 * self.dilation[2]).checked_sub(self.padding[2].0).filter(|&o2| o2 < self.output_shape[2]);
                 if let (Some(o0), Some(o1), Some(o2)) = (o0, o1, o2) {
                     output[[o0, o1, o2, k4]] += input[[i0, i1, i2, i3]] * self.kernel[[k0, k1, k2, i3, k4]]
                 }
@@ -129,16 +134,16 @@ impl<
         });
         output.iter_mut_indexed().for_each(|(oi, t)| {
             f(oi, *t);
-            *t = self.activation.activate(*t)
+            *t = self.activation.activate_at(oi[3], *t)
         });
         output
     }
 }
 
 impl<
-    T: Number,
+    T: Number + Into<f64>,
     B: BackendProvider<Backend<T>: Clone>,
-    A: Activation<T>,
+    A: Parametric<T>,
     KR: Regularizer<T, B, 5>,
     BR: Regularizer<T, B, 4>,
     AR: Regularizer<T, B, 4>,
@@ -147,7 +152,7 @@ impl<
 > Layer for Deconv3D<T, B, A, KR, BR, AR, KC, BC> {
     type Input = Tensor<T, B, 4>;
     type ReverseInput = Tensor<T, B, 4>;
-    type Internal = (Tensor<T, B, 5>, Tensor<T, B, 4>);
+    type Internal = ((Tensor<T, B, 5>, Tensor<T, B, 4>), A::Parameters);
     type Output = Tensor<T, B, 4>;
     type ReverseOutput = Tensor<T, B, 4>;
 
@@ -187,16 +192,20 @@ impl<
             output,
             move |output_d| {
                 assert_eq!(output_d.shape(), &self.output_shape);
+                let activation_d = activations::back_propagate(
+                    &self.activation,
+                    &mut derivatives,
+                    |o| output_d[o] + activation_reg[o],
+                );
                 let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
                 let mut kernel_d = self.kernel_regularizer.derive(&self.kernel);
                 let bias_d = self.bias_regularizer.derive(&self.bias);// todo: + derivatives;
-                derivatives.iter_mut().zip(output_d.iter().copied().zip(activation_reg.iter().copied())).for_each(|(a, (b, c))| *a *= b + c);
                 self.iter_through_input(|[i0, i1, i2, i3]| {
                     let mut i = T::zero();
                     self.iter_through_kernel(|[k0, k1, k2, k4]| {
-                        let o0 = (i0 * self.dilation[0] + k0 * self.strides[0]).checked_sub(self.padding[0].0);
-                        let o1 = (i1 * self.dilation[1] + k1 * self.strides[1]).checked_sub(self.padding[1].0);
-                        let o2 = (i2 * self.dilation[2] + k2 * self.strides[2]).checked_sub(self.padding[2].0);
+                        let o0 = (i0 * self.strides[0] + k0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&o0| o0 < self.output_shape[0]);
+                        let o1 = (i1 * self.strides[1] + k1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&o1| o1 < self.output_shape[1]);
+                        let o2 = (i2 * self.strides[2] + k2 * self.dilation[2]).checked_sub(self.padding[2].0).filter(|&o2| o2 < self.output_shape[2]);
                         if let (Some(o0), Some(o1), Some(o2)) = (o0, o1, o2) {
                             i += self.kernel[[k0, k1, k2, i3, k4]] * derivatives[[o0, o1, o2, k4]];
                             kernel_d[[k0, k1, k2, i3, k4]] += input[[i0, i1, i2, i3]] * derivatives[[o0, o1, o2, k4]];
@@ -205,12 +214,12 @@ impl<
                     input_d[[i0, i1, i2, i3]] = i;
                 });
 
-                (input_d, (kernel_d, bias_d))
+                (input_d, ((kernel_d, bias_d), activation_d))
             },
         )
     }
 
-    fn update(&mut self, (kernel, bias): &Self::Internal) {
+    fn update(&mut self, ((kernel, bias), activation): &Self::Internal) {
         self.kernel
             .iter_mut()
             .zip(kernel.iter().copied())
@@ -219,5 +228,12 @@ impl<
             .iter_mut()
             .zip(bias.iter().copied())
             .for_each(|(a, b)| *a = self.bias_constraint.constrain(*a - b));
+        self.activation.update(activation);
+    }
+
+    fn regularization(&self) -> f64 {
+        let kernel: f64 = self.kernel_regularizer.regularization(&self.kernel).into();
+        let bias: f64 = self.bias_regularizer.regularization(&self.bias).into();
+        kernel + bias
     }
 }
@@ -2,7 +2,7 @@ use num_traits::Number;
 use tensor::BackendProvider;
 
 use crate::{
-    activations::Activation,
+    activations::Parametric,
     constraints::IntoConstraint,
     data::Uninitialized,
     initializers::IntoInitializer,
@@ -45,9 +45,9 @@ builder::builder! {
 }
 
 impl<
-    T: Number,
+    T: Number + Into<f64>,
     B: BackendProvider<Backend<T>: Clone>,
-    A: Activation<T>,
+    A: Parametric<T>,
     P: IntoPadding<1>,
     S: IntoStride<1>,
     D: IntoDilation<1>,
@@ -82,9 +82,9 @@ impl<
 }
 
 impl<
-    T: Number,
+    T: Number + Into<f64>,
     B: BackendProvider<Backend<T>: Clone>,
-    A: Activation<T>,
+    A: Parametric<T>,
     P: IntoPadding<2>,
     S: IntoStride<2>,
     D: IntoDilation<2>,
@@ -119,9 +119,9 @@ impl<
 }
 
 impl<
-    T: Number,
+    T: Number + Into<f64>,
     B: BackendProvider<Backend<T>: Clone>,
-    A: Activation<T>,
+    A: Parametric<T>,
     P: IntoPadding<3>,
     S: IntoStride<3>,
     D: IntoDilation<3>,
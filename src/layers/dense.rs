@@ -2,7 +2,7 @@ use num_traits::Number;
 use tensor::{BackendProvider, Shape, Tensor};
 
 use crate::{
-    activations::Activation,
+    activations::TensorActivation,
     constraints::{
         Constraint,
         IntoConstraint
@@ -29,7 +29,7 @@ use crate::{
 pub struct Dense<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: TensorActivation<T, B, M>,
     KR,
     BR,
     AR,
@@ -53,7 +53,7 @@ pub struct Dense<
 impl<
     T: Number,
     B: BackendProvider,
-    A: Activation<T>,
+    A: TensorActivation<T, B, M>,
     KR,
     BR,
     AR,
@@ -66,7 +66,7 @@ impl<
     fn new<KI: Initializer<T, B, 2>, BI: Initializer<T, B, M>>(
         input_shape: Shape<N>,
         output_shape: Shape<M>,
-        activation: A,
+        mut activation: A,
         mut kernel_initializer: KI,
         mut bias_initializer: BI,
         kernel_regularizer: KR,
@@ -75,12 +75,13 @@ impl<
         kernel_constraint: KC,
         bias_constraint: BC,
     ) -> Self {
+        let pre_activation_shape = activation.pre_activation_shape(&output_shape);
         Self {
             kernel: kernel_initializer.initialize([
                 input_shape.capacity(),
-                output_shape.capacity(),
+                pre_activation_shape.capacity(),
             ].into()),
-            bias: bias_initializer.initialize(output_shape.clone()),
+            bias: bias_initializer.initialize(pre_activation_shape),
             input_shape,
             output_shape,
             activation,
@@ -92,26 +93,25 @@ impl<
         }
     }
 
-    fn feed_forward<F: FnMut(usize, T)>(&self, input: &Tensor<T, B, N>, mut f: F) -> Tensor<T, B, M> {
+    fn feed_forward(&self, input: &Tensor<T, B, N>) -> (Tensor<T, B, M>, A::State) {
         assert_eq!(input.shape(), &self.input_shape);
 
-        let mut output = Tensor::new(T::zero(), self.bias.shape().clone());
+        let mut pre_activations = Tensor::new(T::zero(), self.bias.shape().clone());
         for o in 0..self.kernel.shape()[1] {
             let mut out = self.bias[o];
             for i in 0..self.kernel.shape()[0] {
                 out += input[i] * self.kernel[[i, o]];
             }
-            output[o] = self.activation.activate(out);
-            f(o, out);
+            pre_activations[o] = out;
         }
-        output
+        self.activation.forward(pre_activations)
     }
 }
 
 impl<
-    T: Number,
+    T: Number + Into<f64>,
     B: BackendProvider,
-    A: Activation<T>,
+    A: TensorActivation<T, B, M>,
     KR: Regularizer<T, B, 2>,
     BR: Regularizer<T, B, M>,
     AR: Regularizer<T, B, M>,
@@ -122,7 +122,7 @@ impl<
 > Layer for Dense<T, B, A, KR, BR, AR, KC, BC, N, M> {
     type Input = Tensor<T, B, N>;
     type ReverseInput = Tensor<T, B, N>;
-    type Internal = (Tensor<T, B, 2>, Tensor<T, B, M>);
+    type Internal = ((Tensor<T, B, 2>, Tensor<T, B, M>), A::Parameters);
     type Output = Tensor<T, B, M>;
     type ReverseOutput = Tensor<T, B, M>;
 
@@ -145,7 +145,7 @@ impl<
         &self,
         input: Self::Input,
     ) -> Self::Output {
-        self.feed_forward(&input, |_, _| {})
+        self.feed_forward(&input).0
     }
 
     fn back_propagate(
@@ -155,34 +155,32 @@ impl<
         Self::Output,
         Self::Computation<'_>,
     ) {
-        let mut derivatives = Tensor::<T, B, _>::new(T::zero(), self.output_shape.clone());
-        let output = self.feed_forward(&input, |i, t| derivatives[i] = self.activation.derive(t));
+        let (output, state) = self.feed_forward(&input);
         let activation_reg = self.activity_regularizer.derive(&output);
         (
             output,
             move |output_d| {
                 assert_eq!(output_d.shape(), &self.output_shape);
-                derivatives
-                    .iter_mut()
-                    .zip(output_d.iter().zip(activation_reg.iter()))
-                    .for_each(|(d, (od, ar))| *d *= *od + *ar);
+                let output_d = self.output_shape.clone().into_tensor(|o| output_d[o] + activation_reg[o]);
+                let (derivatives, activation_d) = self.activation.backward(state, output_d);
                 let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
                 let mut kernel_d = self.kernel_regularizer.derive(&self.kernel);
-                let bias_d = self.bias_regularizer.derive(&self.bias);// todo: + derivatives;
+                let mut bias_d = self.bias_regularizer.derive(&self.bias);
+                bias_d.iter_mut().zip(derivatives.iter().copied()).for_each(|(b, d)| *b += d);
                 for i in 0..self.kernel.shape()[0] {
                     let mut id = T::zero();
                     for o in 0..self.kernel.shape()[1] {
-                        kernel_d[[i, o]] = derivatives[o] * input[i];
+                        kernel_d[[i, o]] += derivatives[o] * input[i];
                         id += derivatives[o] * self.kernel[[i, o]];
                     }
                     input_d[i] = id;
                 }
-                (input_d, (kernel_d, bias_d))
+                (input_d, ((kernel_d, bias_d), activation_d))
             },
         )
     }
 
-    fn update(&mut self, (kernel, bias): &Self::Internal) {
+    fn update(&mut self, ((kernel, bias), activation): &Self::Internal) {
         self.kernel
             .iter_mut()
             .zip(kernel.iter().copied())
@@ -191,6 +189,13 @@ impl<
             .iter_mut()
             .zip(bias.iter().copied())
             .for_each(|(a, b)| *a = self.bias_constraint.constrain(*a - b));
+        self.activation.update(activation);
+    }
+
+    fn regularization(&self) -> f64 {
+        let kernel: f64 = self.kernel_regularizer.regularization(&self.kernel).into();
+        let bias: f64 = self.bias_regularizer.regularization(&self.bias).into();
+        kernel + bias
     }
 }
 
@@ -209,9 +214,9 @@ builder::builder! {
 }
 
 impl<
-    T: Number,
+    T: Number + Into<f64>,
     B: BackendProvider,
-    A: Activation<T>,
+    A: TensorActivation<T, B, M>,
     KI: IntoInitializer<T, B, 2>,
     BI: IntoInitializer<T, B, M>,
     KR: IntoRegularizer<T, B, 2>,
@@ -1,9 +1,9 @@
 use std::{
+    array,
     marker::PhantomData,
-    ops::Mul,
 };
 
-use num_traits::Number;
+use num_traits::Float;
 use tensor::{BackendProvider, Shape, Tensor};
 use void::Void;
 
@@ -13,36 +13,112 @@ use crate::{
         FromRef,
         Uninitialized,
     },
-    layers::{Layer, LayerBuilder},
+    layers::{fibers, Layer, LayerBuilder},
 };
 
-pub struct Dot<T, B> {
-    shape: Shape<1>,
-    // axes: (usize, usize),
+pub trait IntoAxes<const N: usize, const M: usize> {
+    fn into_axes(self) -> (usize, usize);
+}
+
+impl<const N: usize, const M: usize> IntoAxes<N, M> for Uninitialized {
+    fn into_axes(self) -> (usize, usize) {
+        (N - 1, 0)
+    }
+}
+
+impl<const N: usize, const M: usize> IntoAxes<N, M> for (usize, usize) {
+    fn into_axes(self) -> (usize, usize) {
+        self
+    }
+}
+
+pub trait IntoNormalize {
+    fn into_normalize(self) -> bool;
+}
+
+impl IntoNormalize for Uninitialized {
+    fn into_normalize(self) -> bool {
+        false
+    }
+}
+
+impl IntoNormalize for bool {
+    fn into_normalize(self) -> bool {
+        self
+    }
+}
+
+/// Contracts axis `axes.0` of the first input with axis `axes.1` of the second input
+///
+/// The output holds the remaining axes of the first input followed by the remaining axes of the second input.
+/// When `normalize` is set every fiber is divided by its L2 norm, which gives the cosine similarity.
+pub struct Dot<T, B, const N: usize, const M: usize> where [(); N + M - 2]: {
+    input_shapes: (Shape<N>, Shape<M>),
+    output_shape: Shape<{ N + M - 2 }>,
+    fibers: (Vec<Vec<usize>>, Vec<Vec<usize>>),
+    normalize: bool,
     _marker: PhantomData<(T, B)>,
 }
 
-impl<T: Number, B: BackendProvider> Dot<T, B> where for<'s> &'s T: Mul<&'s T, Output=T> {
-    pub fn new(shape: Shape<1>/*, axes: (usize, usize)*/) -> Self {
+impl<T: Float, B: BackendProvider, const N: usize, const M: usize> Dot<T, B, N, M> where [(); N + M - 2]: {
+    pub fn new(input_shapes: (Shape<N>, Shape<M>), axes: (usize, usize), normalize: bool) -> Self {
+        let (shape0, shape1) = &input_shapes;
+        assert!(axes.0 < N && axes.1 < M);
+        assert_eq!(shape0[axes.0], shape1[axes.1]);
+        let output_shape = Shape::new(array::from_fn(|i| if i + 1 < N {
+            shape0[if i < axes.0 { i } else { i + 1 }]
+        } else {
+            let i = i + 1 - N;
+            shape1[if i < axes.1 { i } else { i + 1 }]
+        }));
         Self {
-            shape,
-            // axes,
+            fibers: (
+                fibers(shape0, Some(axes.0)).map(Iterator::collect).collect(),
+                fibers(shape1, Some(axes.1)).map(Iterator::collect).collect(),
+            ),
+            input_shapes,
+            output_shape,
+            normalize,
             _marker: PhantomData,
         }
     }
 
-    fn feed_forward(&self, a: &Tensor<T, B, 1>, b: &Tensor<T, B, 1>) -> Tensor<T, B, 0> {
-        let t = a.iter().zip(b.iter()).map(|(a, b)| a * b).sum::<T>();
-        todo!()
+    fn norms<const K: usize>(&self, t: &Tensor<T, B, K>, fibers: &[Vec<usize>]) -> Vec<T> {
+        fibers.iter().map(|f| if self.normalize {
+            f.iter().map(|&i| t[i] * t[i]).sum::<T>().sqrt()
+        } else {
+            T::one()
+        }).collect()
+    }
+
+    /// Returns the output together with the norms of the fibers of both inputs
+    fn feed_forward(&self, a: &Tensor<T, B, N>, b: &Tensor<T, B, M>) -> (Tensor<T, B, { N + M - 2 }>, (Vec<T>, Vec<T>)) {
+        assert_eq!(a.shape(), &self.input_shapes.0);
+        assert_eq!(b.shape(), &self.input_shapes.1);
+
+        let (fibers0, fibers1) = &self.fibers;
+        let norms = (self.norms(a, fibers0), self.norms(b, fibers1));
+        let output = self.output_shape.clone().into_tensor(|o| {
+            let (i, j) = (o / fibers1.len(), o % fibers1.len());
+            let norm = norms.0[i] * norms.1[j];
+            if norm == T::zero() {
+                return T::zero();
+            }
+            fibers0[i].iter()
+                .zip(&fibers1[j])
+                .map(|(&i, &j)| a[i] * b[j])
+                .sum::<T>() / norm
+        });
+        (output, norms)
     }
 }
 
-impl<T: Number, B: BackendProvider> Layer for Dot<T, B> where for<'s> &'s T: Mul<&'s T, Output=T> {
-    type Input = [Tensor<T, B, 1>; 2];
-    type ReverseInput = [Tensor<T, B, 1>; 2];
+impl<T: Float, B: BackendProvider<Backend<T>: Clone>, const N: usize, const M: usize> Layer for Dot<T, B, N, M> where [(); N + M - 2]: {
+    type Input = (Tensor<T, B, N>, Tensor<T, B, M>);
+    type ReverseInput = (Tensor<T, B, N>, Tensor<T, B, M>);
     type Internal = [Void; 0];
-    type Output = Tensor<T, B, 0>;
-    type ReverseOutput = Tensor<T, B, 0>;
+    type Output = Tensor<T, B, { N + M - 2 }>;
+    type ReverseOutput = Tensor<T, B, { N + M - 2 }>;
     type Computation<'s> = impl FnOnce(
         Self::ReverseOutput
     ) -> (
@@ -51,24 +127,45 @@ impl<T: Number, B: BackendProvider> Layer for Dot<T, B> where for<'s> &'s T: Mul
     ) + 's where Self: 's;
 
     fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
-        [&self.shape; 2]
+        (&self.input_shapes.0, &self.input_shapes.1)
     }
 
     fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
-        const ZERO_SHAPE: Shape<0> = Shape::zero();
-        &ZERO_SHAPE
+        &self.output_shape
     }
 
-    fn feed_forward(&self, [a, b]: Self::Input) -> Self::Output {
-        self.feed_forward(&a, &b)
+    fn feed_forward(&self, (a, b): Self::Input) -> Self::Output {
+        self.feed_forward(&a, &b).0
     }
 
-    fn back_propagate(&self, [mut a, mut b]: Self::Input) -> (Self::Output, Self::Computation<'_>) {
-        (self.feed_forward(&a, &b), |output_d| {
-            let output_d = *output_d;
-            a *= output_d;
-            b *= output_d;
-            ([b, a], [])
+    fn back_propagate(&self, (a, b): Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        let (output, norms) = self.feed_forward(&a, &b);
+        (output.clone(), move |output_d| {
+            assert_eq!(output_d.shape(), &self.output_shape);
+            let (fibers0, fibers1) = &self.fibers;
+            let mut a_d = Tensor::new(T::zero(), self.input_shapes.0.clone());
+            let mut b_d = Tensor::new(T::zero(), self.input_shapes.1.clone());
+            for i in 0..fibers0.len() {
+                for j in 0..fibers1.len() {
+                    let o = i * fibers1.len() + j;
+                    let norm = norms.0[i] * norms.1[j];
+                    if norm == T::zero() {
+                        continue;
+                    }
+                    let d = output_d[o] / norm;
+                    // d(cos)/da = b / (|a||b|) - cos * a / |a|^2
+                    let (a_scale, b_scale) = if self.normalize {
+                        (output_d[o] * output[o] / (norms.0[i] * norms.0[i]), output_d[o] * output[o] / (norms.1[j] * norms.1[j]))
+                    } else {
+                        (T::zero(), T::zero())
+                    };
+                    for (&ai, &bi) in fibers0[i].iter().zip(&fibers1[j]) {
+                        a_d[ai] += d * b[bi] - a_scale * a[ai];
+                        b_d[bi] += d * a[ai] - b_scale * b[bi];
+                    }
+                }
+            }
+            ((a_d, b_d), [])
         })
     }
 
@@ -76,20 +173,71 @@ impl<T: Number, B: BackendProvider> Layer for Dot<T, B> where for<'s> &'s T: Mul
 }
 
 builder::builder! {
-    pub struct Builder<(T), (B)> {
-        // axis0: A0,
-        // axis1: A1,
+    pub struct Builder<(T), (B), const N: usize, const M: usize> {
+        axes: AX,
+        normalize: NO,
     }
 }
 
-impl<T: Number, B: BackendProvider> LayerBuilder for Builder<T, B> where for<'s> &'s T: Mul<&'s T, Output=T> {
-    type Layer = Dot<T, B>;
+impl<
+    T: Float,
+    B: BackendProvider<Backend<T>: Clone>,
+    AX: IntoAxes<N, M>,
+    NO: IntoNormalize,
+    const N: usize,
+    const M: usize,
+> LayerBuilder for Builder<AX, NO, T, B, usizeContainer<N>, usizeContainer<M>> where [(); N + M - 2]: {
+    type Layer = Dot<T, B, N, M>;
 
-    fn build(self, [shape0, shape1]: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
-        assert_eq!(shape0, shape1);
+    fn build(self, input_shapes: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
         Self::Layer::new(
-            shape0,
-            // (self.axis0, self.axis1)
+            input_shapes,
+            self.axes.into_axes(),
+            self.normalize.into_normalize(),
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tensor::{Shape, Tensor, VecProvider};
+
+    use crate::{
+        layers::Layer,
+        testing::{assert_gradient, numeric_gradient, tensor, values, weighted_sum},
+    };
+    use super::Dot;
+
+    fn check_gradient(normalize: bool) {
+        let dot = Dot::<f64, VecProvider, 2, 2>::new((Shape::new([2, 3]), Shape::new([4, 3])), (1, 1), normalize);
+        let a = tensor([2, 3], &values(6, 1));
+        let b = tensor([4, 3], &values(12, 2));
+        let weights = tensor([2, 4], &values(8, 3));
+        let objective = |a: &Tensor<f64, VecProvider, 2>, b: &Tensor<f64, VecProvider, 2>| {
+            weighted_sum(&Layer::feed_forward(&dot, (a.clone(), b.clone())), &weights)
+        };
+        let a_numeric = numeric_gradient(&a, |a| objective(a, &b));
+        let b_numeric = numeric_gradient(&b, |b| objective(&a, b));
+        let (_, computation) = Layer::back_propagate(&dot, (a, b));
+        let ((a_d, b_d), []) = computation(weights.clone());
+        assert_gradient(&a_d, &a_numeric);
+        assert_gradient(&b_d, &b_numeric);
+    }
+
+    #[test]
+    fn dot_gradient() {
+        check_gradient(false);
+    }
+
+    #[test]
+    fn cosine_gradient() {
+        check_gradient(true);
+    }
+
+    #[test]
+    fn cosine_of_parallel_fibers_is_one() {
+        let dot = Dot::<f64, VecProvider, 1, 1>::new((Shape::new([3]), Shape::new([3])), (0, 0), true);
+        let output = Layer::feed_forward(&dot, (tensor([3], &[1., 2., 3.]), tensor([3], &[2., 4., 6.])));
+        assert!((output[0] - 1.).abs() < 1e-12);
+    }
+}
@@ -19,33 +19,134 @@ use crate::{
         IntoRegularizer,
         Regularizer,
     },
-    data::{Package, FromRef, Uninitialized}
+    data::{Package, FromRef, Masked, Uninitialized}
 };
 
-pub struct Embedding<T, B: BackendProvider, R, C> {
-    output_shape: Shape<1>,
+/// Padding index of an [`Embedding`], which decides whether its output is [`Masked`]
+pub trait PaddingIndex<T, B: BackendProvider> {
+    type Output: Package<Shapes=Shape<2>>;
+
+    fn index(&self) -> Option<usize>;
+
+    fn output(&self, value: Tensor<T, B, 2>, input: &Tensor<usize, B, 1>) -> Self::Output;
+}
+
+/// No padding index, the [`Embedding`] outputs a plain tensor
+pub struct NoPadding;
+
+impl<T, B: BackendProvider> PaddingIndex<T, B> for NoPadding {
+    type Output = Tensor<T, B, 2>;
+
+    fn index(&self) -> Option<usize> {
+        None
+    }
+
+    fn output(&self, value: Tensor<T, B, 2>, _: &Tensor<usize, B, 1>) -> Self::Output {
+        value
+    }
+}
+
+impl<T, B: BackendProvider> PaddingIndex<T, B> for usize {
+    type Output = Masked<Tensor<T, B, 2>, B>;
+
+    fn index(&self) -> Option<usize> {
+        Some(*self)
+    }
+
+    fn output(&self, value: Tensor<T, B, 2>, input: &Tensor<usize, B, 1>) -> Self::Output {
+        Masked {
+            value,
+            mask: input.shape().clone().into_tensor(|i| input[i] != *self),
+        }
+    }
+}
+
+pub trait IntoPaddingIndex {
+    type PaddingIndex;
+
+    fn into_padding_index(self) -> Self::PaddingIndex;
+}
+
+impl IntoPaddingIndex for Uninitialized {
+    type PaddingIndex = NoPadding;
+
+    fn into_padding_index(self) -> Self::PaddingIndex {
+        NoPadding
+    }
+}
+
+impl IntoPaddingIndex for usize {
+    type PaddingIndex = usize;
+
+    fn into_padding_index(self) -> Self::PaddingIndex {
+        self
+    }
+}
+
+/// Maps a sequence of token indices to a `[sequence, output_size]` tensor of learned vectors
+///
+/// With a padding index the output is [`Masked`] with `false` at every occurrence of it,
+/// otherwise the output is a plain tensor. The padding index never receives a gradient.
+///
+/// `regularizer` penalizes the output vectors, `embeddings_regularizer` penalizes the whole table.
+pub struct Embedding<T, B: BackendProvider, P, R, ER, C> {
+    input_shape: Shape<1>,
+    output_shape: Shape<2>,
+    padding_index: P,
     embedding: Tensor<T, B, 2>,
     regularizer: R,
+    embeddings_regularizer: ER,
     constraint: C,
 }
 
-impl<T: Number, B: BackendProvider, R, C> Embedding<T, B, R, C> {
-    pub fn new<I: Initializer<T, B, 2>>(max_size: usize, output_shape: usize, mut initializer: I, regularizer: R, constraint: C) -> Self {
+impl<T: Number, B: BackendProvider, P: PaddingIndex<T, B>, R, ER, C> Embedding<T, B, P, R, ER, C> {
+    pub fn new<I: Initializer<T, B, 2>>(
+        input_shape: Shape<1>,
+        input_size: usize,
+        output_size: usize,
+        padding_index: P,
+        mut initializer: I,
+        regularizer: R,
+        embeddings_regularizer: ER,
+        constraint: C,
+    ) -> Self {
+        assert!(padding_index.index().map_or(true, |p| p < input_size));
+        let mut embedding = initializer.initialize([input_size, output_size].into());
+        if let Some(p) = padding_index.index() {
+            (0..output_size).for_each(|i| embedding[[p, i]] = T::zero());
+        }
         Self {
-            output_shape: [output_shape].into(),
-            embedding: initializer.initialize([max_size, output_shape].into()),
+            output_shape: [input_shape[0], output_size].into(),
+            input_shape,
+            padding_index,
+            embedding,
             regularizer,
+            embeddings_regularizer,
             constraint,
         }
     }
+
+    fn lookup(&self, input: &Tensor<usize, B, 1>) -> Tensor<T, B, 2> {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let l = self.output_shape[1];
+        self.output_shape.clone().into_tensor(|i| self.embedding[[input[i / l], i % l]])
+    }
 }
 
-impl<T: Number, B: BackendProvider, R: Regularizer<T, B, 1>, C: Constraint<T>> Layer for Embedding<T, B, R, C> {
-    type Input = Tensor<usize, B, 0>;
+impl<
+    T: Number + Into<f64>,
+    B: BackendProvider,
+    P: PaddingIndex<T, B>,
+    R: Regularizer<T, B, 2>,
+    ER: Regularizer<T, B, 2>,
+    C: Constraint<T>,
+> Layer for Embedding<T, B, P, R, ER, C> {
+    type Input = Tensor<usize, B, 1>;
     type ReverseInput = [Void; 0];
     type Internal = Tensor<T, B, 2>;
-    type Output = Tensor<T, B, 1>;
-    type ReverseOutput = Tensor<T, B, 1>;
+    type Output = P::Output;
+    type ReverseOutput = Tensor<T, B, 2>;
 
     type Computation<'s> = impl FnOnce(
         Self::ReverseOutput
@@ -55,8 +156,7 @@ impl<T: Number, B: BackendProvider, R: Regularizer<T, B, 1>, C: Constraint<T>> L
     ) + 's where Self: 's;
 
     fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
-        static ZERO_SHAPE: Shape<0> = Shape::zero();
-        &ZERO_SHAPE
+        &self.input_shape
     }
 
     fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
@@ -67,8 +167,8 @@ impl<T: Number, B: BackendProvider, R: Regularizer<T, B, 1>, C: Constraint<T>> L
         &self,
         input: Self::Input,
     ) -> Self::Output {
-        let index = *input;
-        self.output_shape.clone().into_tensor(|i| self.embedding[[index, i]])
+        let value = self.lookup(&input);
+        self.padding_index.output(value, &input)
     }
 
     fn back_propagate(
@@ -78,17 +178,23 @@ impl<T: Number, B: BackendProvider, R: Regularizer<T, B, 1>, C: Constraint<T>> L
         Self::Output,
         Self::Computation<'_>,
     ) {
-        let index = *input;
-        let output = self.feed_forward(input);
-        let regularization = self.regularizer.derive(&output);
+        let value = self.lookup(&input);
+        let regularization = self.regularizer.derive(&value);
+        let output = self.padding_index.output(value, &input);
+        let indices = input.iter().copied().collect::<Vec<_>>();
         (
             output,
             move |output_d| {
                 assert_eq!(output_d.shape(), &self.output_shape);
                 let l = self.output_shape[1];
-                let mut internal_d = Tensor::new(T::zero(), self.embedding.shape().clone());
-                for i in 0..l {
-                    internal_d[[index, i]] = output_d[i] + regularization[i]
+                let mut internal_d = self.embeddings_regularizer.derive(&self.embedding);
+                for (s, index) in indices.into_iter().enumerate() {
+                    for i in 0..l {
+                        internal_d[[index, i]] += output_d[[s, i]] + regularization[[s, i]]
+                    }
+                }
+                if let Some(p) = self.padding_index.index() {
+                    (0..l).for_each(|i| internal_d[[p, i]] = T::zero());
                 }
                 ([], internal_d)
             }
@@ -101,32 +207,44 @@ impl<T: Number, B: BackendProvider, R: Regularizer<T, B, 1>, C: Constraint<T>> L
             .zip(update.iter().copied())
             .for_each(|(a, b)| *a = self.constraint.constrain(*a - b));
     }
+
+    fn regularization(&self) -> f64 {
+        self.embeddings_regularizer.regularization(&self.embedding).into()
+    }
 }
 
 builder::builder! {
     pub struct Builder<(T), (B)> {
-        output_size: S,
+        input_size: IS,
+        output_size: OS,
+        padding_index: P,
         initializer: I,
         regularizer: R,
+        embeddings_regularizer: ER,
         constraint: C,
     }
 }
 
 impl<
-    T: Number,
+    T: Number + Into<f64>,
     B: BackendProvider,
+    P: IntoPaddingIndex<PaddingIndex: PaddingIndex<T, B>>,
     I: IntoInitializer<T, B, 2>,
-    R: IntoRegularizer<T, B, 1>,
+    R: IntoRegularizer<T, B, 2>,
+    ER: IntoRegularizer<T, B, 2>,
     C: IntoConstraint<T>,
-> LayerBuilder for Builder<usize, I, R, C, T, B> {
-    type Layer = Embedding<T, B, R::Regularizer, C::Constraint>;
+> LayerBuilder for Builder<usize, usize, P, I, R, ER, C, T, B> {
+    type Layer = Embedding<T, B, P::PaddingIndex, R::Regularizer, ER::Regularizer, C::Constraint>;
 
     fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
         Self::Layer::new(
-            input_shape[0],
+            input_shape,
+            self.input_size,
             self.output_size,
+            self.padding_index.into_padding_index(),
             self.initializer.into_initializer(),
             self.regularizer.into_regularizer(),
+            self.embeddings_regularizer.into_regularizer(),
             self.constraint.into_constraint(),
         )
     }
@@ -0,0 +1,130 @@
+use std::marker::PhantomData;
+
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    data::{Package, FromRef, Initialized, Uninitialized},
+    layers::{Layer, LayerBuilder},
+};
+
+pub trait IntoOutputShape<const N: usize, const M: usize> {
+    fn into_output_shape(self, input_shape: &Shape<N>) -> Shape<M>;
+}
+
+impl<const N: usize> IntoOutputShape<N, N> for Uninitialized {
+    fn into_output_shape(self, input_shape: &Shape<N>) -> Shape<N> {
+        input_shape.clone()
+    }
+}
+
+impl<F: FnOnce(&Shape<N>) -> Shape<M> + Initialized, const N: usize, const M: usize> IntoOutputShape<N, M> for F {
+    fn into_output_shape(self, input_shape: &Shape<N>) -> Shape<M> {
+        self(input_shape)
+    }
+}
+
+/// Stateless [`Layer`] built from closures
+///
+/// `backward` takes the input and the derivative with respect to the output
+/// and returns the derivative with respect to the input.
+pub struct Lambda<T, B, F, G, const N: usize, const M: usize> {
+    input_shape: Shape<N>,
+    output_shape: Shape<M>,
+    forward: F,
+    backward: G,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<T, B, F, G, const N: usize, const M: usize> Lambda<T, B, F, G, N, M> {
+    pub fn new(input_shape: Shape<N>, output_shape: Shape<M>, forward: F, backward: G) -> Self {
+        Self {
+            input_shape,
+            output_shape,
+            forward,
+            backward,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<
+    T,
+    B: BackendProvider,
+    F: Fn(&Tensor<T, B, N>) -> Tensor<T, B, M>,
+    G: Fn(&Tensor<T, B, N>, &Tensor<T, B, M>) -> Tensor<T, B, N>,
+    const N: usize,
+    const M: usize,
+> Layer for Lambda<T, B, F, G, N, M> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Tensor<T, B, M>;
+    type ReverseOutput = Tensor<T, B, M>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let output = (self.forward)(&input);
+        assert_eq!(output.shape(), &self.output_shape);
+        output
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let output = (self.forward)(&input);
+        assert_eq!(output.shape(), &self.output_shape);
+        (
+            output,
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let input_d = (self.backward)(&input, &output_d);
+                assert_eq!(input_d.shape(), &self.input_shape);
+                (input_d, [])
+            }
+        )
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize, const M: usize> {
+        forward: F,
+        backward: G,
+        output_shape: S,
+    }
+}
+
+impl<
+    T,
+    B: BackendProvider,
+    F: Fn(&Tensor<T, B, N>) -> Tensor<T, B, M>,
+    G: Fn(&Tensor<T, B, N>, &Tensor<T, B, M>) -> Tensor<T, B, N>,
+    S: IntoOutputShape<N, M>,
+    const N: usize,
+    const M: usize,
+> LayerBuilder for Builder<F, G, S, T, B, usizeContainer<N>, usizeContainer<M>> {
+    type Layer = Lambda<T, B, F, G, N, M>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let output_shape = self.output_shape.into_output_shape(&input_shape);
+        Self::Layer::new(input_shape, output_shape, self.forward, self.backward)
+    }
+}
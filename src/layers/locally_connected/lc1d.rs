@@ -0,0 +1,276 @@
+use std::ops::Add;
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, SliceIndex, Tensor};
+
+use crate::{
+    activations::{self, Parametric},
+    constraints::Constraint,
+    initializers::Initializer,
+    regularizers::Regularizer,
+    data::{Package, FromRef},
+    layers::{
+        convolution::Padding,
+        Layer,
+    },
+};
+
+/// Convolution-like layer whose kernel is not shared between output positions
+pub struct LocallyConnected1D<
+    T: Number,
+    B: BackendProvider,
+    A: Parametric<T>,
+    KR,
+    BR,
+    AR,
+    KC,
+    BC,
+> {
+    input_shape: Shape<2>,
+    output_shape: Shape<2>,
+    kernel: Tensor<T, B, 4>,
+    bias: Tensor<T, B, 2>,
+    activation: A,
+    padding: [(usize, usize); 1],
+    strides: [usize; 1],
+    kernel_regularizer: KR,
+    bias_regularizer: BR,
+    activity_regularizer: AR,
+    kernel_constraint: KC,
+    bias_constraint: BC,
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    A: Parametric<T>,
+    KR,
+    BR,
+    AR,
+    KC,
+    BC,
+> LocallyConnected1D<T, B, A, KR, BR, AR, KC, BC> {
+    pub(crate) fn new<KI: Initializer<T, B, 4>, BI: Initializer<T, B, 2>>(
+        input_shape: Shape<2>,
+        filters: usize,
+        kernel_shape: [usize; 1],
+        mut kernel_initializer: KI,
+        mut bias_initializer: BI,
+        mut activation: A,
+        padding: [Padding; 1],
+        strides: [usize; 1],
+        kernel_regularizer: KR,
+        bias_regularizer: BR,
+        activity_regularizer: AR,
+        kernel_constraint: KC,
+        bias_constraint: BC,
+    ) -> Self {
+        let padding = [padding[0].resolve(input_shape[0], kernel_shape[0], strides[0], 1)];
+        let output_shape = Shape::new([
+            (input_shape[0] + padding[0].0 + padding[0].1 - kernel_shape[0]) / strides[0] + 1,
+            filters,
+        ]);
+        activation.build(filters);
+        Self {
+            kernel: kernel_initializer.initialize([output_shape[0], kernel_shape[0], input_shape[1], filters].into()),
+            bias: bias_initializer.initialize(output_shape.clone()),
+            input_shape,
+            output_shape,
+            activation,
+            padding,
+            strides,
+            kernel_regularizer,
+            bias_regularizer,
+            activity_regularizer,
+            kernel_constraint,
+            bias_constraint,
+        }
+    }
+
+    fn iter_through_output<F: FnMut([usize; 2])>(&self, mut f: F) {
+        for o0 in 0..self.output_shape[0] {
+            for o1 in 0..self.output_shape[1] {
+                f([o0, o1])
+            }
+        }
+    }
+
+    fn iter_through_kernel<F: FnMut([usize; 2])>(&self, mut f: F) {
+        for k0 in 0..self.kernel.shape()[1] {
+            for k1 in 0..self.kernel.shape()[2] {
+                f([k0, k1])
+            }
+        }
+    }
+
+    fn input_index(&self, o0: usize, k0: usize) -> Option<usize> {
+        (o0 * self.strides[0] + k0).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0])
+    }
+
+    fn feed_forward<F: FnMut([usize; 2], T)>(&self, input: &Tensor<T, B, 2>, mut f: F) -> Tensor<T, B, 2> {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let mut output = Tensor::new(T::zero(), self.output_shape.clone());
+        self.iter_through_output(|[o0, o1]| {
+            let mut o = self.bias[[o0, o1]];
+            self.iter_through_kernel(|[k0, k1]| {
+                if let Some(i0) = self.input_index(o0, k0) {
+                    o += input[[i0, k1]] * self.kernel[[o0, k0, k1, o1]]
+                }
+            });
+            f([o0, o1], o);
+            o = self.activation.activate_at(o1, o);
+            output[[o0, o1]] = o
+        });
+        output
+    }
+}
+
+impl<
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
+    B: BackendProvider,
+    A: Parametric<T>,
+    KR: Regularizer<T, B, 4>,
+    BR: Regularizer<T, B, 2>,
+    AR: Regularizer<T, B, 2>,
+    KC: Constraint<T>,
+    BC: Constraint<T>,
+> Layer for LocallyConnected1D<T, B, A, KR, BR, AR, KC, BC> {
+    type Input = Tensor<T, B, 2>;
+    type ReverseInput = Tensor<T, B, 2>;
+    type Internal = ((Tensor<T, B, 4>, Tensor<T, B, 2>), A::Parameters);
+    type Output = Tensor<T, B, 2>;
+    type ReverseOutput = Tensor<T, B, 2>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(
+        &self,
+        input: Self::Input,
+    ) -> Self::Output {
+        self.feed_forward(&input, |_, _| {})
+    }
+
+    fn back_propagate(
+        &self,
+        input: Self::Input,
+    ) -> (
+        Self::Output,
+        Self::Computation<'_>,
+    ) {
+        let mut derivatives = Tensor::<T, B, 2>::new(T::zero(), self.output_shape.clone());
+        let output = self.feed_forward(&input, |o, t| derivatives[o] = t);
+        let activation_reg = self.activity_regularizer.derive(&output);
+        (
+            output,
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let activation_d = activations::back_propagate(
+                    &self.activation,
+                    &mut derivatives,
+                    |o| output_d[o] + activation_reg[o],
+                );
+                let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
+                let mut kernel_d = self.kernel_regularizer.derive(&self.kernel);
+                let bias_d = self.bias_regularizer.derive(&self.bias) + derivatives.slice(SliceIndex::full()).unwrap();
+                self.iter_through_output(|[o0, o1]| {
+                    self.iter_through_kernel(|[k0, k1]| {
+                        if let Some(i0) = self.input_index(o0, k0) {
+                            kernel_d[[o0, k0, k1, o1]] += input[[i0, k1]] * derivatives[[o0, o1]];
+                            input_d[[i0, k1]] += self.kernel[[o0, k0, k1, o1]] * derivatives[[o0, o1]];
+                        }
+                    })
+                });
+                (input_d, ((kernel_d, bias_d), activation_d))
+            }
+        )
+    }
+
+    fn update(&mut self, ((kernel, bias), activation): &Self::Internal) {
+        self.kernel
+            .iter_mut()
+            .zip(kernel.iter().copied())
+            .for_each(|(a, b)| *a = self.kernel_constraint.constrain(*a - b));
+        self.bias
+            .iter_mut()
+            .zip(bias.iter().copied())
+            .for_each(|(a, b)| *a = self.bias_constraint.constrain(*a - b));
+        self.activation.update(activation);
+    }
+
+    fn regularization(&self) -> f64 {
+        let kernel: f64 = self.kernel_regularizer.regularization(&self.kernel).into();
+        let bias: f64 = self.bias_regularizer.regularization(&self.bias).into();
+        kernel + bias
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tensor::{Shape, Tensor, VecProvider};
+
+    use crate::{
+        activations::tanh::Tanh,
+        constraints::none::None as NoneCon,
+        layers::{convolution::Padding, Layer},
+        regularizers::none::None as NoneReg,
+        testing::{assert_gradient, numeric_gradient, tensor, values, weighted_sum},
+    };
+    use super::LocallyConnected1D;
+
+    type LC1D = LocallyConnected1D<f64, VecProvider, Tanh<f64>, NoneReg<f64>, NoneReg<f64>, NoneReg<f64>, NoneCon<f64>, NoneCon<f64>>;
+
+    fn layer(kernel: &Tensor<f64, VecProvider, 4>, bias: &Tensor<f64, VecProvider, 2>) -> LC1D {
+        let mut layer = LC1D::new(
+            Shape::new([5, 2]),
+            3,
+            [3],
+            0f64,
+            0f64,
+            Tanh::new(),
+            [Padding::Symmetrical(1)],
+            [2],
+            NoneReg::new(),
+            NoneReg::new(),
+            NoneReg::new(),
+            NoneCon::new(),
+            NoneCon::new(),
+        );
+        layer.kernel = kernel.clone();
+        layer.bias = bias.clone();
+        layer
+    }
+
+    #[test]
+    fn gradient() {
+        let kernel = tensor([3, 3, 2, 3], &values(54, 1));
+        let bias = tensor([3, 3], &values(9, 2));
+        let input = tensor([5, 2], &values(10, 3));
+        let weights = tensor([3, 3], &values(9, 4));
+        let objective = |kernel: &Tensor<f64, VecProvider, 4>, bias: &Tensor<f64, VecProvider, 2>, input: &Tensor<f64, VecProvider, 2>| {
+            weighted_sum(&Layer::feed_forward(&layer(kernel, bias), input.clone()), &weights)
+        };
+        let input_numeric = numeric_gradient(&input, |input| objective(&kernel, &bias, input));
+        let kernel_numeric = numeric_gradient(&kernel, |kernel| objective(kernel, &bias, &input));
+        let bias_numeric = numeric_gradient(&bias, |bias| objective(&kernel, bias, &input));
+
+        let layer = layer(&kernel, &bias);
+        let (_, computation) = Layer::back_propagate(&layer, input.clone());
+        let (input_d, ((kernel_d, bias_d), [])) = computation(weights.clone());
+        assert_gradient(&input_d, &input_numeric);
+        assert_gradient(&kernel_d, &kernel_numeric);
+        assert_gradient(&bias_d, &bias_numeric);
+    }
+}
@@ -0,0 +1,286 @@
+use std::ops::Add;
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, SliceIndex, Tensor};
+
+use crate::{
+    activations::{self, Parametric},
+    constraints::Constraint,
+    initializers::Initializer,
+    regularizers::Regularizer,
+    data::{Package, FromRef},
+    layers::{
+        convolution::Padding,
+        Layer,
+    },
+};
+
+/// Convolution-like layer whose kernel is not shared between output positions
+pub struct LocallyConnected2D<
+    T: Number,
+    B: BackendProvider,
+    A: Parametric<T>,
+    KR,
+    BR,
+    AR,
+    KC,
+    BC,
+> {
+    input_shape: Shape<3>,
+    output_shape: Shape<3>,
+    kernel: Tensor<T, B, 6>,
+    bias: Tensor<T, B, 3>,
+    activation: A,
+    padding: [(usize, usize); 2],
+    strides: [usize; 2],
+    kernel_regularizer: KR,
+    bias_regularizer: BR,
+    activity_regularizer: AR,
+    kernel_constraint: KC,
+    bias_constraint: BC,
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    A: Parametric<T>,
+    KR,
+    BR,
+    AR,
+    KC,
+    BC,
+> LocallyConnected2D<T, B, A, KR, BR, AR, KC, BC> {
+    pub(crate) fn new<KI: Initializer<T, B, 6>, BI: Initializer<T, B, 3>>(
+        input_shape: Shape<3>,
+        filters: usize,
+        kernel_shape: [usize; 2],
+        mut kernel_initializer: KI,
+        mut bias_initializer: BI,
+        mut activation: A,
+        padding: [Padding; 2],
+        strides: [usize; 2],
+        kernel_regularizer: KR,
+        bias_regularizer: BR,
+        activity_regularizer: AR,
+        kernel_constraint: KC,
+        bias_constraint: BC,
+    ) -> Self {
+        let padding = [
+            padding[0].resolve(input_shape[0], kernel_shape[0], strides[0], 1),
+            padding[1].resolve(input_shape[1], kernel_shape[1], strides[1], 1),
+        ];
+        let output_shape = Shape::new([
+            (input_shape[0] + padding[0].0 + padding[0].1 - kernel_shape[0]) / strides[0] + 1,
+            (input_shape[1] + padding[1].0 + padding[1].1 - kernel_shape[1]) / strides[1] + 1,
+            filters,
+        ]);
+        activation.build(filters);
+        Self {
+            kernel: kernel_initializer.initialize([output_shape[0], output_shape[1], kernel_shape[0], kernel_shape[1], input_shape[2], filters].into()),
+            bias: bias_initializer.initialize(output_shape.clone()),
+            input_shape,
+            output_shape,
+            activation,
+            padding,
+            strides,
+            kernel_regularizer,
+            bias_regularizer,
+            activity_regularizer,
+            kernel_constraint,
+            bias_constraint,
+        }
+    }
+
+    fn iter_through_output<F: FnMut([usize; 3])>(&self, mut f: F) {
+        for o0 in 0..self.output_shape[0] {
+            for o1 in 0..self.output_shape[1] {
+                for o2 in 0..self.output_shape[2] {
+                    f([o0, o1, o2])
+                }
+            }
+        }
+    }
+
+    fn iter_through_kernel<F: FnMut([usize; 3])>(&self, mut f: F) {
+        for k0 in 0..self.kernel.shape()[2] {
+            for k1 in 0..self.kernel.shape()[3] {
+                for k2 in 0..self.kernel.shape()[4] {
+                    f([k0, k1, k2])
+                }
+            }
+        }
+    }
+
+    fn input_index(&self, [o0, o1]: [usize; 2], [k0, k1]: [usize; 2]) -> Option<[usize; 2]> {
+        let i0 = (o0 * self.strides[0] + k0).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
+        let i1 = (o1 * self.strides[1] + k1).checked_sub(self.padding[1].0).filter(|&i1| i1 < self.input_shape[1]);
+        i0.zip(i1).map(|(i0, i1)| [i0, i1])
+    }
+
+    fn feed_forward<F: FnMut([usize; 3], T)>(&self, input: &Tensor<T, B, 3>, mut f: F) -> Tensor<T, B, 3> {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let mut output = Tensor::new(T::zero(), self.output_shape.clone());
+        self.iter_through_output(|[o0, o1, o2]| {
+            let mut o = self.bias[[o0, o1, o2]];
+            self.iter_through_kernel(|[k0, k1, k2]| {
+                if let Some([i0, i1]) = self.input_index([o0, o1], [k0, k1]) {
+                    o += input[[i0, i1, k2]] * self.kernel[[o0, o1, k0, k1, k2, o2]]
+                }
+            });
+            f([o0, o1, o2], o);
+            o = self.activation.activate_at(o2, o);
+            output[[o0, o1, o2]] = o
+        });
+        output
+    }
+}
+
+impl<
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
+    B: BackendProvider,
+    A: Parametric<T>,
+    KR: Regularizer<T, B, 6>,
+    BR: Regularizer<T, B, 3>,
+    AR: Regularizer<T, B, 3>,
+    KC: Constraint<T>,
+    BC: Constraint<T>,
+> Layer for LocallyConnected2D<T, B, A, KR, BR, AR, KC, BC> {
+    type Input = Tensor<T, B, 3>;
+    type ReverseInput = Tensor<T, B, 3>;
+    type Internal = ((Tensor<T, B, 6>, Tensor<T, B, 3>), A::Parameters);
+    type Output = Tensor<T, B, 3>;
+    type ReverseOutput = Tensor<T, B, 3>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(
+        &self,
+        input: Self::Input,
+    ) -> Self::Output {
+        self.feed_forward(&input, |_, _| {})
+    }
+
+    fn back_propagate(
+        &self,
+        input: Self::Input,
+    ) -> (
+        Self::Output,
+        Self::Computation<'_>,
+    ) {
+        let mut derivatives = Tensor::<T, B, 3>::new(T::zero(), self.output_shape.clone());
+        let output = self.feed_forward(&input, |o, t| derivatives[o] = t);
+        let activation_reg = self.activity_regularizer.derive(&output);
+        (
+            output,
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let activation_d = activations::back_propagate(
+                    &self.activation,
+                    &mut derivatives,
+                    |o| output_d[o] + activation_reg[o],
+                );
+                let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
+                let mut kernel_d = self.kernel_regularizer.derive(&self.kernel);
+                let bias_d = self.bias_regularizer.derive(&self.bias) + derivatives.slice(SliceIndex::full()).unwrap();
+                self.iter_through_output(|[o0, o1, o2]| {
+                    self.iter_through_kernel(|[k0, k1, k2]| {
+                        if let Some([i0, i1]) = self.input_index([o0, o1], [k0, k1]) {
+                            kernel_d[[o0, o1, k0, k1, k2, o2]] += input[[i0, i1, k2]] * derivatives[[o0, o1, o2]];
+                            input_d[[i0, i1, k2]] += self.kernel[[o0, o1, k0, k1, k2, o2]] * derivatives[[o0, o1, o2]];
+                        }
+                    })
+                });
+                (input_d, ((kernel_d, bias_d), activation_d))
+            }
+        )
+    }
+
+    fn update(&mut self, ((kernel, bias), activation): &Self::Internal) {
+        self.kernel
+            .iter_mut()
+            .zip(kernel.iter().copied())
+            .for_each(|(a, b)| *a = self.kernel_constraint.constrain(*a - b));
+        self.bias
+            .iter_mut()
+            .zip(bias.iter().copied())
+            .for_each(|(a, b)| *a = self.bias_constraint.constrain(*a - b));
+        self.activation.update(activation);
+    }
+
+    fn regularization(&self) -> f64 {
+        let kernel: f64 = self.kernel_regularizer.regularization(&self.kernel).into();
+        let bias: f64 = self.bias_regularizer.regularization(&self.bias).into();
+        kernel + bias
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tensor::{Shape, Tensor, VecProvider};
+
+    use crate::{
+        activations::tanh::Tanh,
+        constraints::none::None as NoneCon,
+        layers::{convolution::Padding, Layer},
+        regularizers::none::None as NoneReg,
+        testing::{assert_gradient, numeric_gradient, tensor, values, weighted_sum},
+    };
+    use super::LocallyConnected2D;
+
+    type LC2D = LocallyConnected2D<f64, VecProvider, Tanh<f64>, NoneReg<f64>, NoneReg<f64>, NoneReg<f64>, NoneCon<f64>, NoneCon<f64>>;
+
+    fn layer(kernel: &Tensor<f64, VecProvider, 6>, bias: &Tensor<f64, VecProvider, 3>) -> LC2D {
+        let mut layer = LC2D::new(
+            Shape::new([4, 4, 2]),
+            2,
+            [2, 2],
+            0f64,
+            0f64,
+            Tanh::new(),
+            [Padding::None; 2],
+            [2, 1],
+            NoneReg::new(),
+            NoneReg::new(),
+            NoneReg::new(),
+            NoneCon::new(),
+            NoneCon::new(),
+        );
+        layer.kernel = kernel.clone();
+        layer.bias = bias.clone();
+        layer
+    }
+
+    #[test]
+    fn gradient() {
+        let kernel = tensor([2, 3, 2, 2, 2, 2], &values(96, 1));
+        let bias = tensor([2, 3, 2], &values(12, 2));
+        let input = tensor([4, 4, 2], &values(32, 3));
+        let weights = tensor([2, 3, 2], &values(12, 4));
+        let objective = |kernel: &Tensor<f64, VecProvider, 6>, bias: &Tensor<f64, VecProvider, 3>, input: &Tensor<f64, VecProvider, 3>| {
+            weighted_sum(&Layer::feed_forward(&layer(kernel, bias), input.clone()), &weights)
+        };
+        let input_numeric = numeric_gradient(&input, |input| objective(&kernel, &bias, input));
+        let kernel_numeric = numeric_gradient(&kernel, |kernel| objective(kernel, &bias, &input));
+        let bias_numeric = numeric_gradient(&bias, |bias| objective(&kernel, bias, &input));
+
+        let layer = layer(&kernel, &bias);
+        let (_, computation) = Layer::back_propagate(&layer, input.clone());
+        let (input_d, ((kernel_d, bias_d), [])) = computation(weights.clone());
+        assert_gradient(&input_d, &input_numeric);
+        assert_gradient(&kernel_d, &kernel_numeric);
+        assert_gradient(&bias_d, &bias_numeric);
+    }
+}
@@ -0,0 +1,111 @@
+use std::ops::Add;
+
+use num_traits::Number;
+use tensor::BackendProvider;
+
+use crate::{
+    activations::Parametric,
+    constraints::IntoConstraint,
+    data::{Package, Uninitialized},
+    initializers::IntoInitializer,
+    layers::{
+        convolution::{IntoPadding, IntoStride},
+        locally_connected::{
+            lc1d::LocallyConnected1D,
+            lc2d::LocallyConnected2D,
+        },
+        Layer,
+        LayerBuilder,
+    },
+    regularizers::IntoRegularizer,
+};
+
+mod lc1d;
+mod lc2d;
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize> {
+        kernel_shape: SHAPE,
+        filters: F,
+        activation: A,
+        padding: P,
+        strides: S,
+        kernel_initializer: KI,
+        bias_initializer: BI,
+        kernel_regularizer: KR,
+        bias_regularizer: BR,
+        activity_regularizer: AR,
+        kernel_constraint: KC,
+        bias_constraint: BC,
+    }
+}
+
+impl<
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
+    B: BackendProvider,
+    A: Parametric<T>,
+    P: IntoPadding<1>,
+    S: IntoStride<1>,
+    KI: IntoInitializer<T, B, 4>,
+    BI: IntoInitializer<T, B, 2>,
+    KR: IntoRegularizer<T, B, 4>,
+    BR: IntoRegularizer<T, B, 2>,
+    AR: IntoRegularizer<T, B, 2>,
+    KC: IntoConstraint<T>,
+    BC: IntoConstraint<T>,
+> LayerBuilder for Builder<[usize; 1], usize, A, P, S, KI, BI, KR, BR, AR, KC, BC, T, B, usizeContainer<1>> {
+    type Layer = LocallyConnected1D<T, B, A, KR::Regularizer, BR::Regularizer, AR::Regularizer, KC::Constraint, BC::Constraint>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(
+            input_shape,
+            self.filters,
+            self.kernel_shape,
+            self.kernel_initializer.into_initializer(),
+            self.bias_initializer.into_initializer(),
+            self.activation,
+            self.padding.into_padding(),
+            self.strides.into_stride(),
+            self.kernel_regularizer.into_regularizer(),
+            self.bias_regularizer.into_regularizer(),
+            self.activity_regularizer.into_regularizer(),
+            self.kernel_constraint.into_constraint(),
+            self.bias_constraint.into_constraint(),
+        )
+    }
+}
+
+impl<
+    T: Number + Into<f64> + for<'s> Add<&'s T, Output=T>,
+    B: BackendProvider,
+    A: Parametric<T>,
+    P: IntoPadding<2>,
+    S: IntoStride<2>,
+    KI: IntoInitializer<T, B, 6>,
+    BI: IntoInitializer<T, B, 3>,
+    KR: IntoRegularizer<T, B, 6>,
+    BR: IntoRegularizer<T, B, 3>,
+    AR: IntoRegularizer<T, B, 3>,
+    KC: IntoConstraint<T>,
+    BC: IntoConstraint<T>,
+> LayerBuilder for Builder<[usize; 2], usize, A, P, S, KI, BI, KR, BR, AR, KC, BC, T, B, usizeContainer<2>> {
+    type Layer = LocallyConnected2D<T, B, A, KR::Regularizer, BR::Regularizer, AR::Regularizer, KC::Constraint, BC::Constraint>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(
+            input_shape,
+            self.filters,
+            self.kernel_shape,
+            self.kernel_initializer.into_initializer(),
+            self.bias_initializer.into_initializer(),
+            self.activation,
+            self.padding.into_padding(),
+            self.strides.into_stride(),
+            self.kernel_regularizer.into_regularizer(),
+            self.bias_regularizer.into_regularizer(),
+            self.activity_regularizer.into_regularizer(),
+            self.kernel_constraint.into_constraint(),
+            self.bias_constraint.into_constraint(),
+        )
+    }
+}
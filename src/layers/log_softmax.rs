@@ -0,0 +1,154 @@
+use std::marker::PhantomData;
+
+use num_traits::Float;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    activations::softmax::softmax,
+    data::{Package, FromRef, Uninitialized},
+    layers::{
+        fibers,
+        Layer,
+        LayerBuilder,
+        masked::MaskPreserving,
+        softmax::IntoAxis,
+    },
+    losses::log_sum_exp,
+};
+
+/// Logarithm of [`SoftMax`] along `axis`, or over the whole tensor when `axis` is `None`
+///
+/// [`SoftMax`]: crate::layers::softmax::SoftMax
+pub struct LogSoftMax<T, B: BackendProvider, const N: usize> {
+    shape: Shape<N>,
+    axis: Option<usize>,
+    _maker: PhantomData<(T, B)>,
+}
+
+impl<T, B: BackendProvider, const N: usize> LogSoftMax<T, B, N> {
+    fn new(shape: Shape<N>, axis: Option<usize>) -> Self {
+        assert!(axis.map_or(true, |axis| axis < N));
+        Self {
+            shape,
+            axis,
+            _maker: PhantomData,
+        }
+    }
+}
+
+impl<T: Float, B: BackendProvider, const N: usize> LogSoftMax<T, B, N> {
+    /// Returns the output together with the softmax probabilities
+    fn feed_forward(&self, input: &Tensor<T, B, N>) -> (Tensor<T, B, N>, Tensor<T, B, N>) {
+        assert_eq!(input.shape(), &self.shape);
+
+        let mut probabilities = Tensor::new(T::zero(), self.shape.clone());
+        fibers(&self.shape, self.axis).for_each(|f| softmax(input, f, &mut probabilities));
+        let mut output = Tensor::new(T::zero(), self.shape.clone());
+        fibers(&self.shape, self.axis).for_each(|f| {
+            let log_sum = log_sum_exp(f.clone().map(|i| input[i]));
+            f.for_each(|i| output[i] = input[i] - log_sum);
+        });
+        (output, probabilities)
+    }
+}
+
+impl<T: Float, B: BackendProvider, const N: usize> Layer for LogSoftMax<T, B, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Tensor<T, B, N>;
+    type ReverseOutput = Tensor<T, B, N>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.shape
+    }
+
+    fn feed_forward(
+        &self,
+        input: Self::Input,
+    ) -> Self::Output {
+        self.feed_forward(&input).0
+    }
+
+    fn back_propagate(
+        &self,
+        input: Self::Input,
+    ) -> (
+        Self::Output,
+        Self::Computation<'_>,
+    ) {
+        let (output, probabilities) = self.feed_forward(&input);
+        (
+            output,
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.shape);
+                let mut input_d = Tensor::new(T::zero(), self.shape.clone());
+                fibers(&self.shape, self.axis).for_each(|f| {
+                    let sum = f.clone().map(|i| output_d[i]).sum::<T>();
+                    f.for_each(|i| input_d[i] = output_d[i] - probabilities[i] * sum);
+                });
+                (input_d, [])
+            }
+        )
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+impl<T: Float, B: BackendProvider, const N: usize> MaskPreserving for LogSoftMax<T, B, N> {}
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize> {
+        axis: AX,
+    }
+}
+
+impl<T: Float, B: BackendProvider, AX: IntoAxis, const N: usize> LayerBuilder for Builder<AX, T, B, usizeContainer<N>> {
+    type Layer = LogSoftMax<T, B, N>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(input_shape, self.axis.into_axis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tensor::{Shape, VecProvider};
+
+    use crate::{
+        layers::Layer,
+        testing::{assert_close, check_input_gradient, tensor, values},
+    };
+    use super::LogSoftMax;
+
+    #[test]
+    fn gradient_along_an_axis() {
+        let layer = LogSoftMax::<f64, VecProvider, 2>::new(Shape::new([3, 4]), Some(0));
+        check_input_gradient(&layer, tensor([3, 4], &values(12, 1)));
+    }
+
+    #[test]
+    fn gradient_over_the_whole_tensor() {
+        let layer = LogSoftMax::<f64, VecProvider, 2>::new(Shape::new([3, 4]), None);
+        check_input_gradient(&layer, tensor([3, 4], &values(12, 2)));
+    }
+
+    #[test]
+    fn fibers_along_the_axis_sum_to_one() {
+        let layer = LogSoftMax::<f64, VecProvider, 2>::new(Shape::new([3, 4]), Some(0));
+        let output = Layer::feed_forward(&layer, tensor([3, 4], &values(12, 3)));
+        (0..4).for_each(|j| assert_close((0..3).map(|i| output[[i, j]].exp()).sum(), 1.));
+    }
+}
@@ -0,0 +1,103 @@
+use std::marker::PhantomData;
+
+use tensor::{BackendProvider, Tensor};
+
+use crate::{
+    data::{Package, FromRef, Masked, Uninitialized},
+    layers::{Layer, LayerBuilder},
+};
+
+/// [`Layer`] that can ignore the masked entries of the leading axis of its input
+pub trait MaskAware<B: BackendProvider>: Layer {
+    type MaskedComputation<'s>: FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal
+    ) + 's where Self: 's;
+
+    fn feed_forward_masked(
+        &self,
+        input: Self::Input,
+        mask: &Tensor<bool, B, 1>,
+    ) -> Self::Output;
+
+    fn back_propagate_masked(
+        &self,
+        input: Self::Input,
+        mask: &Tensor<bool, B, 1>,
+    ) -> (
+        Self::Output,
+        Self::MaskedComputation<'_>,
+    );
+}
+
+/// [`Layer`] whose output keeps the leading axis of its input, so that the mask of its input also applies to its output
+///
+/// [`PropagateMask`] forwards the mask through such a layer.
+///
+/// [`PropagateMask`]: crate::layers::propagate_mask::PropagateMask
+pub trait MaskPreserving: Layer {}
+
+/// Wraps a [`MaskAware`] layer so that it consumes a [`Masked`] input
+pub struct MaskedLayer<L, B> {
+    layer: L,
+    _marker: PhantomData<B>,
+}
+
+impl<L, B> MaskedLayer<L, B> {
+    pub fn new(layer: L) -> Self {
+        Self {
+            layer,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B: BackendProvider, L: MaskAware<B>> Layer for MaskedLayer<L, B> {
+    type Input = Masked<L::Input, B>;
+    type ReverseInput = L::ReverseInput;
+    type Internal = L::Internal;
+    type Output = L::Output;
+    type ReverseOutput = L::ReverseOutput;
+
+    type Computation<'s> = L::MaskedComputation<'s> where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        self.layer.input_shapes()
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        self.layer.output_shapes()
+    }
+
+    fn feed_forward(&self, Masked { value, mask }: Self::Input) -> Self::Output {
+        self.layer.feed_forward_masked(value, &mask)
+    }
+
+    fn back_propagate(&self, Masked { value, mask }: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        self.layer.back_propagate_masked(value, &mask)
+    }
+
+    fn update(&mut self, update: &Self::Internal) {
+        self.layer.update(update)
+    }
+
+    fn regularization(&self) -> f64 {
+        self.layer.regularization()
+    }
+}
+
+builder::builder! {
+    pub struct Builder<(B)> {
+        layer: L,
+    }
+}
+
+impl<B: BackendProvider, L: LayerBuilder<Layer: MaskAware<B>>> LayerBuilder for Builder<L, B> {
+    type Layer = MaskedLayer<L::Layer, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(self.layer.build(input_shape))
+    }
+}
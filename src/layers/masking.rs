@@ -0,0 +1,122 @@
+use std::marker::PhantomData;
+
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    data::{Package, FromRef, Initialized, Masked, Uninitialized},
+    layers::{Layer, LayerBuilder},
+};
+
+pub trait IntoMaskValue<T> {
+    fn into_mask_value(self) -> T;
+}
+
+impl<T: Number> IntoMaskValue<T> for Uninitialized {
+    fn into_mask_value(self) -> T {
+        T::zero()
+    }
+}
+
+impl<T: Initialized> IntoMaskValue<T> for T {
+    fn into_mask_value(self) -> T {
+        self
+    }
+}
+
+/// Masks every entry of the leading axis whose values all equal `mask_value`
+///
+/// Masked entries are zeroed in the output and receive no gradient.
+pub struct Masking<T, B, const N: usize> {
+    shape: Shape<N>,
+    mask_shape: Shape<1>,
+    mask_value: T,
+    _marker: PhantomData<B>,
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> Masking<T, B, N> {
+    pub fn new(shape: Shape<N>, mask_value: T) -> Self {
+        assert!(N > 0);
+        Self {
+            mask_shape: [shape[0]].into(),
+            shape,
+            mask_value,
+            _marker: PhantomData,
+        }
+    }
+
+    fn step(&self) -> usize {
+        self.shape.capacity() / self.shape[0]
+    }
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> Layer for Masking<T, B, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Masked<Tensor<T, B, N>, B>;
+    type ReverseOutput = Tensor<T, B, N>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        assert_eq!(input.shape(), &self.shape);
+
+        let step = self.step();
+        let mask = self.mask_shape.clone().into_tensor(|s| {
+            (s * step..(s + 1) * step).any(|i| input[i] != self.mask_value)
+        });
+        Masked {
+            value: self.shape.clone().into_tensor(|i| if mask[i / step] { input[i] } else { T::zero() }),
+            mask,
+        }
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        let output = self.feed_forward(input);
+        let mask = output.mask.iter().copied().collect::<Vec<_>>();
+        (
+            output,
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.shape);
+                let step = self.step();
+                (self.shape.clone().into_tensor(|i| if mask[i / step] { output_d[i] } else { T::zero() }), [])
+            }
+        )
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize> {
+        mask_value: MV,
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    MV: IntoMaskValue<T>,
+    const N: usize,
+> LayerBuilder for Builder<MV, T, B, usizeContainer<N>> {
+    type Layer = Masking<T, B, N>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(input_shape, self.mask_value.into_mask_value())
+    }
+}
@@ -1,19 +1,31 @@
 //! [`Layer`] and [`LayerBuilder`] trait definition
 
+use tensor::Shape;
+
 use crate::data::{Package, FromRef};
 
 pub mod add;
 pub mod activation;
 pub mod convert;
 pub mod convolution;
+pub mod cropping;
 pub mod deconvolution;
 pub mod dot;
 pub mod dense;
 pub mod embedding;
+pub mod lambda;
+pub mod locally_connected;
+pub mod log_softmax;
+pub mod masked;
+pub mod masking;
 pub mod pooling;
+pub mod propagate_mask;
 pub mod reshape;
 pub mod softmax;
 pub mod split;
+pub mod time_distributed;
+pub mod upsampling;
+pub mod zero_padding;
 // todo: add layers
 
 /// [`Layer`]-s are the main building block of the framework.
@@ -50,6 +62,11 @@ pub trait Layer {
     );
 
     fn update(&mut self, update: &Self::Internal);
+
+    /// Penalty that the regularizers of the layer add to the loss
+    fn regularization(&self) -> f64 {
+        0.
+    }
 }
 
 /// Builder trait used when creating a [`Model`]
@@ -60,3 +77,46 @@ pub trait LayerBuilder {
 
     fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer;
 }
+
+/// Calls `f` with every index of `shape` in row-major order
+pub(crate) fn iter_through_shape<const N: usize, F: FnMut([usize; N])>(shape: &Shape<N>, mut f: F) {
+    if (0..N).any(|i| shape[i] == 0) {
+        return;
+    }
+    let mut index = [0; N];
+    'outer: loop {
+        f(index);
+        for i in (0..N).rev() {
+            index[i] += 1;
+            if index[i] < shape[i] {
+                continue 'outer;
+            }
+            index[i] = 0;
+        }
+        break;
+    }
+}
+
+/// Yields the linear indices of every fiber of `shape` along `axis` in row-major order of the remaining axes
+///
+/// With `axis` set to `None` the whole tensor is a single fiber.
+pub(crate) fn fibers<const N: usize>(
+    shape: &Shape<N>,
+    axis: Option<usize>,
+) -> impl Iterator<Item=impl Iterator<Item=usize> + Clone> {
+    let (outer, length, inner) = match axis {
+        Some(axis) => {
+            assert!(axis < N);
+            (
+                (0..axis).map(|i| shape[i]).product(),
+                shape[axis],
+                (axis + 1..N).map(|i| shape[i]).product(),
+            )
+        }
+        None => (1, shape.capacity(), 1),
+    };
+    (0..outer * inner).map(move |f| {
+        let (o, i) = (f / inner, f % inner);
+        (0..length).map(move |k| (o * length + k) * inner + i)
+    })
+}
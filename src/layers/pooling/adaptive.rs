@@ -0,0 +1,269 @@
+use std::{
+    array,
+    marker::PhantomData,
+};
+
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    data::{Package, FromRef, Uninitialized},
+    layers::{
+        iter_through_shape,
+        Layer,
+        LayerBuilder,
+        pooling::PoolingType,
+    },
+};
+
+/// Pools a rank `N` input into a fixed output size whatever the input size
+///
+/// Output position `o` of a spatial axis covers inputs `floor(o * input / output)..ceil((o + 1) * input / output)`.
+pub struct AdaptivePooling<const S: PoolingType, T, B, const N: usize> {
+    input_shape: Shape<N>,
+    output_shape: Shape<N>,
+    _marker: PhantomData<(T, B)>,
+}
+
+pub type AdaptiveAveragePooling1D<T, B> = AdaptivePooling<{ PoolingType::Average }, T, B, 2>;
+pub type AdaptiveAveragePooling2D<T, B> = AdaptivePooling<{ PoolingType::Average }, T, B, 3>;
+pub type AdaptiveAveragePooling3D<T, B> = AdaptivePooling<{ PoolingType::Average }, T, B, 4>;
+pub type AdaptiveMaxPooling1D<T, B> = AdaptivePooling<{ PoolingType::Max }, T, B, 2>;
+pub type AdaptiveMaxPooling2D<T, B> = AdaptivePooling<{ PoolingType::Max }, T, B, 3>;
+pub type AdaptiveMaxPooling3D<T, B> = AdaptivePooling<{ PoolingType::Max }, T, B, 4>;
+
+impl<const S: PoolingType, T, B, const N: usize> AdaptivePooling<S, T, B, N> {
+    pub(crate) fn new(input_shape: Shape<N>, output_shape: Shape<N>) -> Self {
+        assert_eq!(input_shape[N - 1], output_shape[N - 1]);
+        assert!((0..N).all(|i| output_shape[i] > 0));
+        assert!((0..N - 1).all(|i| input_shape[i] > 0), "adaptive pooling needs a non-empty input along every spatial axis");
+        Self {
+            input_shape,
+            output_shape,
+            _marker: PhantomData,
+        }
+    }
+
+    fn iter_through_pool<F: FnMut([usize; N])>(&self, oi: [usize; N], mut f: F) {
+        let start: [usize; N] = array::from_fn(|i| if i + 1 < N {
+            oi[i] * self.input_shape[i] / self.output_shape[i]
+        } else {
+            oi[i]
+        });
+        let end: [usize; N] = array::from_fn(|i| if i + 1 < N {
+            ((oi[i] + 1) * self.input_shape[i] + self.output_shape[i] - 1) / self.output_shape[i]
+        } else {
+            oi[i] + 1
+        });
+        let pool_shape = Shape::new(array::from_fn(|i| end[i] - start[i]));
+        iter_through_shape(&pool_shape, |pi| f(array::from_fn(|i| start[i] + pi[i])));
+    }
+}
+
+impl<T: Number + From<i32>, B: BackendProvider, const N: usize> AdaptivePooling<{ PoolingType::Average }, T, B, N> {
+    fn feed_forward<F: FnMut([usize; N], T)>(&self, input: &Tensor<T, B, N>, mut f: F) -> Tensor<T, B, N> {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let mut output = Tensor::new(T::zero(), self.output_shape.clone());
+        iter_through_shape(&self.output_shape, |oi| {
+            let mut o = T::zero();
+            let mut n = 0;
+            self.iter_through_pool(oi, |ii| {
+                o += input[ii];
+                n += 1;
+            });
+            let n = T::from(n);
+            o /= n;
+            f(oi, n);
+            output[oi] = o
+        });
+        output
+    }
+}
+
+impl<T: Number + From<i32>, B: BackendProvider, const N: usize> Layer for AdaptivePooling<{ PoolingType::Average }, T, B, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Tensor<T, B, N>;
+    type ReverseOutput = Tensor<T, B, N>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        self.feed_forward(&input, |_, _| {})
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        let mut coverage = Tensor::<_, B, _>::new(T::zero(), self.output_shape.clone());
+        let output = self.feed_forward(&input, |oi, n| coverage[oi] = n);
+        (
+            output,
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
+                iter_through_shape(&self.output_shape, |oi| {
+                    let output_d = output_d[oi] / coverage[oi];
+                    self.iter_through_pool(oi, |ii| input_d[ii] += output_d);
+                });
+                (input_d, [])
+            }
+        )
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> AdaptivePooling<{ PoolingType::Max }, T, B, N> {
+    fn feed_forward_<F: FnMut([usize; N], [usize; N])>(&self, input: &Tensor<T, B, N>, mut f: F) -> Tensor<T, B, N> {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let mut output = Tensor::new(T::zero(), self.output_shape.clone());
+        iter_through_shape(&self.output_shape, |oi| {
+            let mut mi = None;
+            self.iter_through_pool(oi, |ii| match mi {
+                Some(m) if input[m] >= input[ii] => {}
+                _ => mi = Some(ii),
+            });
+            let mi = mi.unwrap();
+            f(oi, mi);
+            output[oi] = input[mi]
+        });
+        output
+    }
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> Layer for AdaptivePooling<{ PoolingType::Max }, T, B, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Tensor<T, B, N>;
+    type ReverseOutput = Tensor<T, B, N>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        self.feed_forward_(&input, |_, _| {})
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        let mut coverage = Vec::with_capacity(self.output_shape.capacity());
+        let output = self.feed_forward_(&input, |oi, ii| coverage.push((oi, ii)));
+        (
+            output,
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
+                coverage.into_iter().for_each(|(oi, ii)| input_d[ii] += output_d[oi]);
+                (input_d, [])
+            }
+        )
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize, const M: PoolingType> {
+        output_size: SHAPE,
+    }
+}
+
+impl<
+    T: Number + From<i32>,
+    B: BackendProvider,
+> LayerBuilder for Builder<[usize; 1], T, B, usizeContainer<1>, PoolingTypeContainer<{ PoolingType::Average }>> {
+    type Layer = AdaptivePooling<{ PoolingType::Average }, T, B, 2>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let output_shape = [self.output_size[0], input_shape[1]].into();
+        Self::Layer::new(input_shape, output_shape)
+    }
+}
+
+impl<
+    T: Number + From<i32>,
+    B: BackendProvider,
+> LayerBuilder for Builder<[usize; 2], T, B, usizeContainer<2>, PoolingTypeContainer<{ PoolingType::Average }>> {
+    type Layer = AdaptivePooling<{ PoolingType::Average }, T, B, 3>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let output_shape = [self.output_size[0], self.output_size[1], input_shape[2]].into();
+        Self::Layer::new(input_shape, output_shape)
+    }
+}
+
+impl<
+    T: Number + From<i32>,
+    B: BackendProvider,
+> LayerBuilder for Builder<[usize; 3], T, B, usizeContainer<3>, PoolingTypeContainer<{ PoolingType::Average }>> {
+    type Layer = AdaptivePooling<{ PoolingType::Average }, T, B, 4>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let output_shape = [self.output_size[0], self.output_size[1], self.output_size[2], input_shape[3]].into();
+        Self::Layer::new(input_shape, output_shape)
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+> LayerBuilder for Builder<[usize; 1], T, B, usizeContainer<1>, PoolingTypeContainer<{ PoolingType::Max }>> {
+    type Layer = AdaptivePooling<{ PoolingType::Max }, T, B, 2>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let output_shape = [self.output_size[0], input_shape[1]].into();
+        Self::Layer::new(input_shape, output_shape)
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+> LayerBuilder for Builder<[usize; 2], T, B, usizeContainer<2>, PoolingTypeContainer<{ PoolingType::Max }>> {
+    type Layer = AdaptivePooling<{ PoolingType::Max }, T, B, 3>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let output_shape = [self.output_size[0], self.output_size[1], input_shape[2]].into();
+        Self::Layer::new(input_shape, output_shape)
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+> LayerBuilder for Builder<[usize; 3], T, B, usizeContainer<3>, PoolingTypeContainer<{ PoolingType::Max }>> {
+    type Layer = AdaptivePooling<{ PoolingType::Max }, T, B, 4>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let output_shape = [self.output_size[0], self.output_size[1], self.output_size[2], input_shape[3]].into();
+        Self::Layer::new(input_shape, output_shape)
+    }
+}
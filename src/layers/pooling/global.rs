@@ -0,0 +1,260 @@
+use std::marker::PhantomData;
+
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    data::{Package, FromRef, Uninitialized},
+    layers::{
+        Layer,
+        LayerBuilder,
+        masked::MaskAware,
+        pooling::PoolingType,
+    },
+};
+
+/// Pools every axis of a rank `N` input except the trailing channel axis into a `[channels]` tensor
+pub struct GlobalPooling<const S: PoolingType, T, B, const N: usize> {
+    input_shape: Shape<N>,
+    output_shape: Shape<1>,
+    _marker: PhantomData<(T, B)>,
+}
+
+pub type GlobalAveragePooling1D<T, B> = GlobalPooling<{ PoolingType::Average }, T, B, 2>;
+pub type GlobalAveragePooling2D<T, B> = GlobalPooling<{ PoolingType::Average }, T, B, 3>;
+pub type GlobalAveragePooling3D<T, B> = GlobalPooling<{ PoolingType::Average }, T, B, 4>;
+pub type GlobalMaxPooling1D<T, B> = GlobalPooling<{ PoolingType::Max }, T, B, 2>;
+pub type GlobalMaxPooling2D<T, B> = GlobalPooling<{ PoolingType::Max }, T, B, 3>;
+pub type GlobalMaxPooling3D<T, B> = GlobalPooling<{ PoolingType::Max }, T, B, 4>;
+
+impl<const S: PoolingType, T, B, const N: usize> GlobalPooling<S, T, B, N> {
+    pub(crate) fn new(input_shape: Shape<N>) -> Self {
+        assert!((0..N - 1).all(|i| input_shape[i] > 0), "global pooling needs a non-empty input along every spatial axis");
+        Self {
+            output_shape: [input_shape[N - 1]].into(),
+            input_shape,
+            _marker: PhantomData,
+        }
+    }
+
+    fn channels(&self) -> usize {
+        self.output_shape[0]
+    }
+}
+
+impl<const S: PoolingType, T, B: BackendProvider, const N: usize> GlobalPooling<S, T, B, N> {
+    /// Whether linear input index `i` lies in an unmasked entry of the leading axis
+    fn is_unmasked(&self, mask: Option<&Tensor<bool, B, 1>>, i: usize) -> bool {
+        mask.map_or(true, |mask| mask[i / (self.input_shape.capacity() / self.input_shape[0])])
+    }
+}
+
+impl<T: Number + From<i32>, B: BackendProvider, const N: usize> GlobalPooling<{ PoolingType::Average }, T, B, N> {
+    /// Number of input entries pooled into every channel
+    fn count(&self, mask: Option<&Tensor<bool, B, 1>>) -> T {
+        let n = self.input_shape.capacity() / self.channels();
+        match mask {
+            Some(mask) => T::from((mask.iter().filter(|m| **m).count() * n / self.input_shape[0]) as i32),
+            None => T::from(n as i32),
+        }
+    }
+
+    fn feed_forward(&self, input: &Tensor<T, B, N>, mask: Option<&Tensor<bool, B, 1>>) -> Tensor<T, B, 1> {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let channels = self.channels();
+        let mut output = Tensor::new(T::zero(), self.output_shape.clone());
+        input.iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_unmasked(mask, *i))
+            .for_each(|(i, t)| output[i % channels] += *t);
+        let n = self.count(mask);
+        if n != T::zero() {
+            output.iter_mut().for_each(|o| *o /= n);
+        }
+        output
+    }
+
+    fn back_propagate(&self, output_d: Tensor<T, B, 1>, mask: Option<&Tensor<bool, B, 1>>) -> Tensor<T, B, N> {
+        assert_eq!(output_d.shape(), &self.output_shape);
+        let channels = self.channels();
+        let n = self.count(mask);
+        self.input_shape.clone().into_tensor(|i| if self.is_unmasked(mask, i) {
+            output_d[i % channels] / n
+        } else {
+            T::zero()
+        })
+    }
+}
+
+impl<T: Number + From<i32>, B: BackendProvider, const N: usize> Layer for GlobalPooling<{ PoolingType::Average }, T, B, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Tensor<T, B, 1>;
+    type ReverseOutput = Tensor<T, B, 1>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        self.feed_forward(&input, None)
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        (
+            self.feed_forward(&input, None),
+            move |output_d| (self.back_propagate(output_d, None), []),
+        )
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+impl<T: Number + From<i32>, B: BackendProvider<Backend<bool>: Clone>, const N: usize> MaskAware<B> for GlobalPooling<{ PoolingType::Average }, T, B, N> {
+    type MaskedComputation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn feed_forward_masked(&self, input: Self::Input, mask: &Tensor<bool, B, 1>) -> Self::Output {
+        self.feed_forward(&input, Some(mask))
+    }
+
+    fn back_propagate_masked(&self, input: Self::Input, mask: &Tensor<bool, B, 1>) -> (Self::Output, Self::MaskedComputation<'_>) {
+        let mask = mask.clone();
+        (
+            self.feed_forward(&input, Some(&mask)),
+            move |output_d| (self.back_propagate(output_d, Some(&mask)), []),
+        )
+    }
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> GlobalPooling<{ PoolingType::Max }, T, B, N> {
+    fn feed_forward_<F: FnMut(usize, usize)>(&self, input: &Tensor<T, B, N>, mask: Option<&Tensor<bool, B, 1>>, mut f: F) -> Tensor<T, B, 1> {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let channels = self.channels();
+        let mut output = Tensor::new(T::zero(), self.output_shape.clone());
+        for o in 0..channels {
+            let mi = (o..self.input_shape.capacity())
+                .step_by(channels)
+                .filter(|i| self.is_unmasked(mask, *i))
+                .reduce(|mi, i| if input[i] > input[mi] { i } else { mi });
+            if let Some(mi) = mi {
+                f(o, mi);
+                output[o] = input[mi]
+            }
+        }
+        output
+    }
+
+    fn back_propagate_<F: FnOnce(&mut dyn FnMut(usize, usize)) -> Tensor<T, B, 1>>(&self, feed_forward: F) -> (Tensor<T, B, 1>, impl FnOnce(Tensor<T, B, 1>) -> Tensor<T, B, N> + '_) {
+        let mut coverage = Vec::with_capacity(self.channels());
+        let output = feed_forward(&mut |o, i| coverage.push((o, i)));
+        (
+            output,
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
+                coverage.into_iter().for_each(|(o, i)| input_d[i] = output_d[o]);
+                input_d
+            }
+        )
+    }
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> Layer for GlobalPooling<{ PoolingType::Max }, T, B, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Tensor<T, B, 1>;
+    type ReverseOutput = Tensor<T, B, 1>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        self.feed_forward_(&input, None, |_, _| {})
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        let (output, computation) = self.back_propagate_(|f| self.feed_forward_(&input, None, f));
+        (output, move |output_d| (computation(output_d), []))
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> MaskAware<B> for GlobalPooling<{ PoolingType::Max }, T, B, N> {
+    type MaskedComputation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn feed_forward_masked(&self, input: Self::Input, mask: &Tensor<bool, B, 1>) -> Self::Output {
+        self.feed_forward_(&input, Some(mask), |_, _| {})
+    }
+
+    fn back_propagate_masked(&self, input: Self::Input, mask: &Tensor<bool, B, 1>) -> (Self::Output, Self::MaskedComputation<'_>) {
+        let (output, computation) = self.back_propagate_(|f| self.feed_forward_(&input, Some(mask), f));
+        (output, move |output_d| (computation(output_d), []))
+    }
+}
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize, const M: PoolingType> {}
+}
+
+impl<
+    T: Number + From<i32>,
+    B: BackendProvider,
+    const N: usize,
+> LayerBuilder for Builder<T, B, usizeContainer<N>, PoolingTypeContainer<{ PoolingType::Average }>> {
+    type Layer = GlobalPooling<{ PoolingType::Average }, T, B, N>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(input_shape)
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    const N: usize,
+> LayerBuilder for Builder<T, B, usizeContainer<N>, PoolingTypeContainer<{ PoolingType::Max }>> {
+    type Layer = GlobalPooling<{ PoolingType::Max }, T, B, N>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(input_shape)
+    }
+}
@@ -7,6 +7,8 @@ use crate::{
 };
 
 // pub mod pooling;
+pub mod adaptive;
+pub mod global;
 pub mod pooling1d;
 pub mod pooling2d;
 pub mod pooling3d;
@@ -32,7 +32,7 @@ impl<const S: PoolingType, T: Number, B> Pooling1D<S, T, B> {
         strides: [usize; 1],
         dilation: [usize; 1],
     ) -> Self {
-        let padding = padding.map(Padding::resolve);
+        let padding = [padding[0].resolve(input_shape[0], pool_size[0], strides[0], dilation[0])];
         let output_shape = [
             (input_shape[0] + padding[0].0 + padding[0].1 - dilation[0] * (pool_size[0] - 1) - 1) / strides[0] + 1,
             input_shape[1],
@@ -72,7 +72,7 @@ impl<T: Number + From<i32>, B: BackendProvider> Pooling1D<{ PoolingType::Average
             let mut o = T::zero();
             let mut n = 0;
             self.iter_through_pool(|[p0]| {
-                let i0 = (o0 * self.dilation[0] + p0 * self.strides[0]).checked_sub(self.padding[0].0);
+                let i0 = (o0 * self.strides[0] + p0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
                 if let Some(i0) = i0 {
                     o += input[[i0, o1]];
                     n += 1;
@@ -148,7 +148,7 @@ impl<T: Number + Ord, B: BackendProvider> Pooling1D<{ PoolingType::Max }, T, B>
             let mut o = T::zero();
             let mut mi = [0, 0];
             self.iter_through_pool(|[p0]| {
-                let i0 = (o0 * self.dilation[0] + p0 * self.strides[0]).checked_sub(self.padding[0].0);
+                let i0 = (o0 * self.strides[0] + p0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
                 if let Some(i0) = i0 {
                     if input[[i0, o1]] > o {
                         o = input[[i0, o1]];
@@ -31,7 +31,10 @@ impl<const S: PoolingType, T: Number, B> Pooling2D<S, T, B> {
         strides: [usize; 2],
         dilation: [usize; 2],
     ) -> Self {
-        let padding = padding.map(Padding::resolve);
+        let padding = [
+            padding[0].resolve(input_shape[0], pool_size[0], strides[0], dilation[0]),
+            padding[1].resolve(input_shape[1], pool_size[1], strides[1], dilation[1]),
+        ];
         let output_shape = [
             (input_shape[0] + padding[0].0 + padding[0].1 - dilation[0] * (pool_size[0] - 1) - 1) / strides[0] + 1,
             (input_shape[1] + padding[1].0 + padding[1].1 - dilation[1] * (pool_size[1] - 1) - 1) / strides[1] + 1,
@@ -76,8 +79,8 @@ impl<T: Number + From<i32>, B: BackendProvider> Pooling2D<{ PoolingType::Average
             let mut o = T::zero();
             let mut n = 0;
             self.iter_through_pool(|[p0, p1]| {
-                let i0 = (o0 * self.dilation[0] + p0 * self.strides[0]).checked_sub(self.padding[0].0);
-                let i1 = (o1 * self.dilation[1] + p1 * self.strides[1]).checked_sub(self.padding[1].0);
+                let i0 = (o0 * self.strides[0] + p0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
+                let i1 = (o1 * self.strides[1] + p1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&i1| i1 < self.input_shape[1]);
                 if let (Some(i0), Some(i1)) = (i0, i1) {
                     o += input[[i0, i1, o2]];
                     n += 1;
@@ -155,8 +158,8 @@ impl<T: Number + Ord, B: BackendProvider> Pooling2D<{ PoolingType::Max }, T, B>
             let mut o = T::zero();
             let mut mi = [0, 0, 0];
             self.iter_through_pool(|[p0, p1]| {
-                let i0 = (o0 * self.dilation[0] + p0 * self.strides[0]).checked_sub(self.padding[0].0);
-                let i1 = (o1 * self.dilation[1] + p1 * self.strides[1]).checked_sub(self.padding[1].0);
+                let i0 = (o0 * self.strides[0] + p0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
+                let i1 = (o1 * self.strides[1] + p1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&i1| i1 < self.input_shape[1]);
                 if let (Some(i0), Some(i1)) = (i0, i1) {
                     if input[[i0, i1, o2]] > o {
                         o = input[[i0, i1, o2]];
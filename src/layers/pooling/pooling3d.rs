@@ -31,7 +31,11 @@ impl<const S: PoolingType, T: Number, B> Pooling3D<S, T, B> {
         strides: [usize; 3],
         dilation: [usize; 3],
     ) -> Self {
-        let padding = padding.map(Padding::resolve);
+        let padding = [
+            padding[0].resolve(input_shape[0], pool_size[0], strides[0], dilation[0]),
+            padding[1].resolve(input_shape[1], pool_size[1], strides[1], dilation[1]),
+            padding[2].resolve(input_shape[2], pool_size[2], strides[2], dilation[2]),
+        ];
         let output_shape = [
             (input_shape[0] + padding[0].0 + padding[0].1 - dilation[0] * (pool_size[0] - 1) - 1) / strides[0] + 1,
             (input_shape[1] + padding[1].0 + padding[1].1 - dilation[1] * (pool_size[1] - 1) - 1) / strides[1] + 1,
@@ -81,9 +85,9 @@ impl<T: Number + From<i32>, B: BackendProvider> Pooling3D<{ PoolingType::Average
             let mut o = T::zero();
             let mut n = 0;
             self.iter_through_pool(|[p0, p1, p2]| {
-                let i0 = (o0 * self.dilation[0] + p0 * self.strides[0]).checked_sub(self.padding[0].0);
-                let i1 = (o1 * self.dilation[1] + p1 * self.strides[1]).checked_sub(self.padding[1].0);
-                let i2 = (o2 * self.dilation[2] + p2 * self.strides[2]).checked_sub(self.padding[2].0);
+                let i0 = (o0 * self.strides[0] + p0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
+                let i1 = (o1 * self.strides[1] + p1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&i1| i1 < self.input_shape[1]);
+                let i2 = (o2 * self.strides[2] + p2 * self.dilation[2]).checked_sub(self.padding[2].0).filter(|&i2| i2 < self.input_shape[2]);
                 if let (Some(i0), Some(i1), Some(i2)) = (i0, i1, i2) {
                     o += input[[i0, i1, i2, o3]];
                     n += 1;
@@ -162,9 +166,9 @@ impl<T: Number + Ord, B: BackendProvider> Pooling3D<{ PoolingType::Max }, T, B>
             let mut o = T::zero();
             let mut mi = [0, 0, 0, 0];
             self.iter_through_pool(|[p0, p1, p2]| {
-                let i0 = (o0 * self.dilation[0] + p0 * self.strides[0]).checked_sub(self.padding[0].0);
-                let i1 = (o1 * self.dilation[1] + p1 * self.strides[1]).checked_sub(self.padding[1].0);
-                let i2 = (o2 * self.dilation[2] + p2 * self.strides[2]).checked_sub(self.padding[2].0);
+                let i0 = (o0 * self.strides[0] + p0 * self.dilation[0]).checked_sub(self.padding[0].0).filter(|&i0| i0 < self.input_shape[0]);
+                let i1 = (o1 * self.strides[1] + p1 * self.dilation[1]).checked_sub(self.padding[1].0).filter(|&i1| i1 < self.input_shape[1]);
+                let i2 = (o2 * self.strides[2] + p2 * self.dilation[2]).checked_sub(self.padding[2].0).filter(|&i2| i2 < self.input_shape[2]);
                 if let (Some(i0), Some(i1), Some(i2)) = (i0, i1, i2) {
                     if input[[i0, i1, i2, o3]] > o {
                         o = input[[i0, i1, i2, o3]];
@@ -0,0 +1,82 @@
+use std::marker::PhantomData;
+
+use tensor::BackendProvider;
+
+use crate::{
+    data::{Package, FromRef, Masked, Uninitialized},
+    layers::{Layer, LayerBuilder, masked::MaskPreserving},
+};
+
+/// Wraps a [`MaskPreserving`] layer so that it takes a [`Masked`] input and copies the mask to its output
+///
+/// The derivatives of masked entries are left as they are, the masked losses and [`MaskedLayer`] already zero them.
+/// A [`SoftMax`] or [`LogSoftMax`] over the leading axis still mixes masked entries into the others.
+///
+/// [`MaskedLayer`]: crate::layers::masked::MaskedLayer
+/// [`SoftMax`]: crate::layers::softmax::SoftMax
+/// [`LogSoftMax`]: crate::layers::log_softmax::LogSoftMax
+pub struct PropagateMask<L, B> {
+    layer: L,
+    _marker: PhantomData<B>,
+}
+
+impl<L, B> PropagateMask<L, B> {
+    pub fn new(layer: L) -> Self {
+        Self {
+            layer,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B: BackendProvider, L: MaskPreserving> Layer for PropagateMask<L, B> {
+    type Input = Masked<L::Input, B>;
+    type ReverseInput = L::ReverseInput;
+    type Internal = L::Internal;
+    type Output = Masked<L::Output, B>;
+    type ReverseOutput = L::ReverseOutput;
+
+    type Computation<'s> = L::Computation<'s> where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        self.layer.input_shapes()
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        self.layer.output_shapes()
+    }
+
+    fn feed_forward(&self, Masked { value, mask }: Self::Input) -> Self::Output {
+        Masked {
+            value: self.layer.feed_forward(value),
+            mask,
+        }
+    }
+
+    fn back_propagate(&self, Masked { value, mask }: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        let (value, computation) = self.layer.back_propagate(value);
+        (Masked { value, mask }, computation)
+    }
+
+    fn update(&mut self, update: &Self::Internal) {
+        self.layer.update(update)
+    }
+
+    fn regularization(&self) -> f64 {
+        self.layer.regularization()
+    }
+}
+
+builder::builder! {
+    pub struct Builder<(B)> {
+        layer: L,
+    }
+}
+
+impl<B: BackendProvider, L: LayerBuilder<Layer: MaskPreserving>> LayerBuilder for Builder<L, B> {
+    type Layer = PropagateMask<L::Layer, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(self.layer.build(input_shape))
+    }
+}
@@ -1,30 +1,50 @@
-use std::{
-    cmp::Ordering,
-    marker::PhantomData,
-};
+use std::marker::PhantomData;
 
 use num_traits::Float;
 use tensor::{BackendProvider, Shape, Tensor};
 use void::Void;
 
 use crate::{
+    activations::softmax::softmax,
     layers::{
+        fibers,
         Layer,
         LayerBuilder,
+        masked::MaskPreserving,
     },
 };
 use crate::data::{Package, Uninitialized};
 use crate::data::FromRef;
 
+pub trait IntoAxis {
+    fn into_axis(self) -> Option<usize>;
+}
+
+impl IntoAxis for Uninitialized {
+    fn into_axis(self) -> Option<usize> {
+        None
+    }
+}
+
+impl IntoAxis for usize {
+    fn into_axis(self) -> Option<usize> {
+        Some(self)
+    }
+}
+
+/// Normalizes every fiber along `axis` into a probability distribution, or the whole tensor when `axis` is `None`
 pub struct SoftMax<T, B: BackendProvider, const N: usize> {
     shape: Shape<N>,
+    axis: Option<usize>,
     _maker: PhantomData<(T, B)>,
 }
 
 impl<T, B: BackendProvider, const N: usize> SoftMax<T, B, N> {
-    fn new(shape: Shape<N>) -> Self {
+    fn new(shape: Shape<N>, axis: Option<usize>) -> Self {
+        assert!(axis.map_or(true, |axis| axis < N));
         Self {
             shape,
+            axis,
             _maker: PhantomData,
         }
     }
@@ -58,15 +78,8 @@ impl<T: Float, B: BackendProvider<Backend<T>: Clone>, const N: usize> Layer for
     ) -> Self::Output {
         assert_eq!(input.shape(), &self.shape);
 
-        let mut output = input;
-        let max = output
-            .iter()
-            .copied()
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-            .unwrap_or(T::zero());
-        output.iter_mut().for_each(|t| *t = (*t - max).exp());
-        let s = output.iter().copied().sum::<T>();
-        output.iter_mut().for_each(|t| *t /= s);
+        let mut output = Tensor::new(T::zero(), self.shape.clone());
+        fibers(&self.shape, self.axis).for_each(|f| softmax(&input, f, &mut output));
         output
     }
 
@@ -80,14 +93,13 @@ impl<T: Float, B: BackendProvider<Backend<T>: Clone>, const N: usize> Layer for
         let output = self.feed_forward(input);
         (
             output.clone(),
-            move |mut output_d| {
+            move |output_d| {
                 assert_eq!(output_d.shape(), &self.shape);
-                output_d
-                    .iter_mut()
-                    .zip(output.iter().copied())
-                    .for_each(|(a, b)| *a *= b);
-                let sum = output_d.iter().copied().sum::<T>();
-                let input_d = output.shape().clone().into_tensor(|i| output_d[i] - sum * output[i]);
+                let mut input_d = Tensor::new(T::zero(), self.shape.clone());
+                fibers(&self.shape, self.axis).for_each(|f| {
+                    let dot = f.clone().map(|i| output[i] * output_d[i]).sum::<T>();
+                    f.for_each(|i| input_d[i] = output[i] * (output_d[i] - dot));
+                });
                 (input_d, [])
             }
         )
@@ -96,14 +108,18 @@ impl<T: Float, B: BackendProvider<Backend<T>: Clone>, const N: usize> Layer for
     fn update(&mut self, []: &Self::Internal) {}
 }
 
+impl<T: Float, B: BackendProvider<Backend<T>: Clone>, const N: usize> MaskPreserving for SoftMax<T, B, N> {}
+
 builder::builder! {
-    pub struct Builder<(T), (B), const N: usize> {}
+    pub struct Builder<(T), (B), const N: usize> {
+        axis: AX,
+    }
 }
 
-impl<T: Float, B: BackendProvider<Backend<T>: Clone>, const N: usize> LayerBuilder for Builder<T, B, usizeContainer<N>> {
+impl<T: Float, B: BackendProvider<Backend<T>: Clone>, AX: IntoAxis, const N: usize> LayerBuilder for Builder<AX, T, B, usizeContainer<N>> {
     type Layer = SoftMax<T, B, N>;
 
     fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
-        Self::Layer::new(input_shape)
+        Self::Layer::new(input_shape, self.axis.into_axis())
     }
 }
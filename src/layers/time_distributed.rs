@@ -0,0 +1,259 @@
+use std::{
+    array,
+    marker::PhantomData,
+};
+
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, Tensor};
+
+use crate::{
+    data::{Package, FromRef, Uninitialized},
+    layers::{masked::MaskPreserving, Layer, LayerBuilder},
+    optimizers::mini_batch::Combinable,
+};
+
+/// Applies the inner layer independently to every entry of the leading (time) axis
+///
+/// The weights are shared between timesteps and their gradients are summed.
+pub struct TimeDistributed<L, T, B, const N: usize, const M: usize> where [(); N + 1]:, [(); M + 1]: {
+    layer: L,
+    input_shape: Shape<{ N + 1 }>,
+    output_shape: Shape<{ M + 1 }>,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<
+    L: Layer<Input=Tensor<T, B, N>, Output=Tensor<T, B, M>>,
+    T,
+    B: BackendProvider,
+    const N: usize,
+    const M: usize,
+> TimeDistributed<L, T, B, N, M> where [(); N + 1]:, [(); M + 1]: {
+    pub fn new(layer: L, steps: usize) -> Self {
+        assert!(steps > 0, "TimeDistributed needs a non-empty time axis");
+        let input_shape = Shape::new(array::from_fn(|i| if i == 0 { steps } else { layer.input_shapes()[i - 1] }));
+        let output_shape = Shape::new(array::from_fn(|i| if i == 0 { steps } else { layer.output_shapes()[i - 1] }));
+        Self {
+            layer,
+            input_shape,
+            output_shape,
+            _marker: PhantomData,
+        }
+    }
+
+    fn steps(&self) -> usize {
+        self.input_shape[0]
+    }
+}
+
+impl<
+    L: Layer<
+        Input=Tensor<T, B, N>,
+        ReverseInput=Tensor<T, B, N>,
+        Internal: Combinable,
+        Output=Tensor<T, B, M>,
+        ReverseOutput=Tensor<T, B, M>,
+    >,
+    T: Number,
+    B: BackendProvider,
+    const N: usize,
+    const M: usize,
+> Layer for TimeDistributed<L, T, B, N, M> where [(); N + 1]:, [(); M + 1]: {
+    type Input = Tensor<T, B, { N + 1 }>;
+    type ReverseInput = Tensor<T, B, { N + 1 }>;
+    type Internal = L::Internal;
+    type Output = Tensor<T, B, { M + 1 }>;
+    type ReverseOutput = Tensor<T, B, { M + 1 }>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let input_step = self.layer.input_shapes().capacity();
+        let output_step = self.layer.output_shapes().capacity();
+        let outputs = (0..self.steps())
+            .map(|s| self.layer.feed_forward(self.layer.input_shapes().clone().into_tensor(|i| input[s * input_step + i])))
+            .collect::<Vec<_>>();
+        self.output_shape.clone().into_tensor(|o| outputs[o / output_step][o % output_step])
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let input_step = self.layer.input_shapes().capacity();
+        let output_step = self.layer.output_shapes().capacity();
+        let (outputs, computations): (Vec<_>, Vec<_>) = (0..self.steps())
+            .map(|s| self.layer.back_propagate(self.layer.input_shapes().clone().into_tensor(|i| input[s * input_step + i])))
+            .unzip();
+        (
+            self.output_shape.clone().into_tensor(|o| outputs[o / output_step][o % output_step]),
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let mut internal = None;
+                let inputs_d = computations
+                    .into_iter()
+                    .enumerate()
+                    .map(|(s, computation)| {
+                        let (input_d, step_internal) = computation(self.layer.output_shapes().clone().into_tensor(|o| output_d[s * output_step + o]));
+                        internal = Some(match internal.take() {
+                            None => step_internal,
+                            Some(internal) => Combinable::combine(internal, step_internal),
+                        });
+                        input_d
+                    })
+                    .collect::<Vec<_>>();
+                (
+                    self.input_shape.clone().into_tensor(|i| inputs_d[i / input_step][i % input_step]),
+                    internal.expect("time axis is checked to be non-empty on construction"),
+                )
+            }
+        )
+    }
+
+    fn update(&mut self, update: &Self::Internal) {
+        self.layer.update(update)
+    }
+
+    fn regularization(&self) -> f64 {
+        self.layer.regularization()
+    }
+}
+
+impl<
+    L: Layer<
+        Input=Tensor<T, B, N>,
+        ReverseInput=Tensor<T, B, N>,
+        Internal: Combinable,
+        Output=Tensor<T, B, M>,
+        ReverseOutput=Tensor<T, B, M>,
+    >,
+    T: Number,
+    B: BackendProvider,
+    const N: usize,
+    const M: usize,
+> MaskPreserving for TimeDistributed<L, T, B, N, M> where [(); N + 1]:, [(); M + 1]: {}
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize, const M: usize> {
+        layer: L,
+    }
+}
+
+impl<
+    L: LayerBuilder<Layer: Layer<
+        Input=Tensor<T, B, N>,
+        ReverseInput=Tensor<T, B, N>,
+        Internal: Combinable,
+        Output=Tensor<T, B, M>,
+        ReverseOutput=Tensor<T, B, M>,
+    >>,
+    T: Number,
+    B: BackendProvider,
+    const N: usize,
+    const M: usize,
+> LayerBuilder for Builder<L, T, B, usizeContainer<N>, usizeContainer<M>> where [(); N + 1]:, [(); M + 1]: {
+    type Layer = TimeDistributed<L::Layer, T, B, N, M>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let steps = input_shape[0];
+        Self::Layer::new(self.layer.build(Shape::new(array::from_fn(|i| input_shape[i + 1]))), steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tensor::{Shape, Tensor, VecProvider};
+
+    use crate::{
+        data::{FromRef, Package},
+        layers::Layer,
+        testing::{assert_gradient, check_input_gradient, numeric_gradient, tensor, values, weighted_sum},
+    };
+    use super::TimeDistributed;
+
+    /// `tanh(x * weights)`, small enough to check the summed weight gradients by hand
+    struct Scale {
+        shape: Shape<1>,
+        weights: Tensor<f64, VecProvider, 1>,
+    }
+
+    impl Layer for Scale {
+        type Input = Tensor<f64, VecProvider, 1>;
+        type ReverseInput = Tensor<f64, VecProvider, 1>;
+        type Internal = Tensor<f64, VecProvider, 1>;
+        type Output = Tensor<f64, VecProvider, 1>;
+        type ReverseOutput = Tensor<f64, VecProvider, 1>;
+
+        type Computation<'s> = impl FnOnce(
+            Self::ReverseOutput
+        ) -> (
+            Self::ReverseInput,
+            Self::Internal,
+        ) + 's where Self: 's;
+
+        fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+            &self.shape
+        }
+
+        fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+            &self.shape
+        }
+
+        fn feed_forward(&self, input: Self::Input) -> Self::Output {
+            self.shape.clone().into_tensor(|i| (input[i] * self.weights[i]).tanh())
+        }
+
+        fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+            let output = Layer::feed_forward(self, input.clone());
+            (
+                output.clone(),
+                move |output_d| {
+                    let d = self.shape.clone().into_tensor(|i| output_d[i] * (1. - output[i] * output[i]));
+                    (
+                        self.shape.clone().into_tensor(|i| d[i] * self.weights[i]),
+                        self.shape.clone().into_tensor(|i| d[i] * input[i]),
+                    )
+                }
+            )
+        }
+
+        fn update(&mut self, _: &Self::Internal) {}
+    }
+
+    fn layer(weights: &Tensor<f64, VecProvider, 1>) -> TimeDistributed<Scale, f64, VecProvider, 1, 1> {
+        TimeDistributed::new(Scale { shape: Shape::new([4]), weights: weights.clone() }, 3)
+    }
+
+    #[test]
+    fn input_gradient() {
+        check_input_gradient(&layer(&tensor([4], &values(4, 1))), tensor([3, 4], &values(12, 2)));
+    }
+
+    #[test]
+    fn weight_gradients_are_summed_over_timesteps() {
+        let weights = tensor([4], &values(4, 1));
+        let input = tensor([3, 4], &values(12, 2));
+        let output_d = tensor([3, 4], &values(12, 3));
+        let numeric = numeric_gradient(&weights, |weights| {
+            weighted_sum(&Layer::feed_forward(&layer(weights), input.clone()), &output_d)
+        });
+        let layer = layer(&weights);
+        let (_, computation) = Layer::back_propagate(&layer, input);
+        let (_, weights_d) = computation(output_d.clone());
+        assert_gradient(&weights_d, &numeric);
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    array,
+    marker::PhantomData,
+};
+
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    data::{Package, FromRef, Uninitialized},
+    layers::{
+        iter_through_shape,
+        Layer,
+        LayerBuilder,
+    },
+};
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+pub enum Interpolation {
+    Nearest,
+    /// Linear, bilinear or trilinear depending on the number of spatial axes
+    Linear,
+}
+
+pub trait IntoInterpolation {
+    fn into_interpolation(self) -> Interpolation;
+}
+
+impl IntoInterpolation for Uninitialized {
+    fn into_interpolation(self) -> Interpolation {
+        Interpolation::Nearest
+    }
+}
+
+impl IntoInterpolation for Interpolation {
+    fn into_interpolation(self) -> Interpolation {
+        self
+    }
+}
+
+/// Repeats the spatial axes of a channel-last rank `N` input `size` times
+pub struct UpSampling<T, B, const N: usize> {
+    input_shape: Shape<N>,
+    output_shape: Shape<N>,
+    size: [usize; N],
+    interpolation: Interpolation,
+    _marker: PhantomData<(T, B)>,
+}
+
+pub type UpSampling1D<T, B> = UpSampling<T, B, 2>;
+pub type UpSampling2D<T, B> = UpSampling<T, B, 3>;
+pub type UpSampling3D<T, B> = UpSampling<T, B, 4>;
+
+impl<T: Number + From<i32>, B: BackendProvider, const N: usize> UpSampling<T, B, N> {
+    pub(crate) fn new(input_shape: Shape<N>, size: [usize; N], interpolation: Interpolation) -> Self {
+        assert_eq!(size[N - 1], 1);
+        Self {
+            output_shape: Shape::new(array::from_fn(|i| input_shape[i] * size[i])),
+            input_shape,
+            size,
+            interpolation,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Calls `f` with every input index that contributes to `oi` and its weight
+    fn iter_through_sources<F: FnMut([usize; N], T)>(&self, oi: [usize; N], mut f: F) {
+        match self.interpolation {
+            Interpolation::Nearest => f(array::from_fn(|i| oi[i] / self.size[i]), T::from(1)),
+            Interpolation::Linear => {
+                // input position of `oi` is `(oi + 0.5) / size - 0.5`, kept as a fraction over `2 * size`
+                let axes: [(usize, usize, T); N] = array::from_fn(|i| {
+                    let scale = 2 * self.size[i] as i32;
+                    let position = (2 * oi[i] as i32 + 1 - self.size[i] as i32).max(0);
+                    let i0 = ((position / scale) as usize).min(self.input_shape[i] - 1);
+                    let i1 = (i0 + 1).min(self.input_shape[i] - 1);
+                    (i0, i1, T::from(position % scale) / T::from(scale))
+                });
+                for corner in 0..1 << (N - 1) {
+                    let mut weight = T::from(1);
+                    let ii = array::from_fn(|i| if corner >> i & 1 == 1 {
+                        weight *= axes[i].2;
+                        axes[i].1
+                    } else {
+                        weight *= T::from(1) - axes[i].2;
+                        axes[i].0
+                    });
+                    f(ii, weight)
+                }
+            }
+        }
+    }
+}
+
+impl<T: Number + From<i32>, B: BackendProvider, const N: usize> Layer for UpSampling<T, B, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Tensor<T, B, N>;
+    type ReverseOutput = Tensor<T, B, N>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let mut output = Tensor::new(T::zero(), self.output_shape.clone());
+        iter_through_shape(&self.output_shape, |oi| {
+            let mut o = T::zero();
+            self.iter_through_sources(oi, |ii, w| o += input[ii] * w);
+            output[oi] = o
+        });
+        output
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        (
+            self.feed_forward(input),
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
+                iter_through_shape(&self.output_shape, |oi| {
+                    self.iter_through_sources(oi, |ii, w| input_d[ii] += output_d[oi] * w)
+                });
+                (input_d, [])
+            }
+        )
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize> {
+        size: SIZE,
+        interpolation: I,
+    }
+}
+
+impl<
+    T: Number + From<i32>,
+    B: BackendProvider,
+    I: IntoInterpolation,
+> LayerBuilder for Builder<[usize; 1], I, T, B, usizeContainer<1>> {
+    type Layer = UpSampling1D<T, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(
+            input_shape,
+            [self.size[0], 1],
+            self.interpolation.into_interpolation(),
+        )
+    }
+}
+
+impl<
+    T: Number + From<i32>,
+    B: BackendProvider,
+    I: IntoInterpolation,
+> LayerBuilder for Builder<[usize; 2], I, T, B, usizeContainer<2>> {
+    type Layer = UpSampling2D<T, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(
+            input_shape,
+            [self.size[0], self.size[1], 1],
+            self.interpolation.into_interpolation(),
+        )
+    }
+}
+
+impl<
+    T: Number + From<i32>,
+    B: BackendProvider,
+    I: IntoInterpolation,
+> LayerBuilder for Builder<[usize; 3], I, T, B, usizeContainer<3>> {
+    type Layer = UpSampling3D<T, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        Self::Layer::new(
+            input_shape,
+            [self.size[0], self.size[1], self.size[2], 1],
+            self.interpolation.into_interpolation(),
+        )
+    }
+}
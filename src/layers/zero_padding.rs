@@ -0,0 +1,135 @@
+use std::{
+    array,
+    marker::PhantomData,
+};
+
+use num_traits::Number;
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    data::{Package, FromRef, Uninitialized},
+    layers::{
+        convolution::{resolve_unit_kernel, IntoPadding},
+        iter_through_shape,
+        Layer,
+        LayerBuilder,
+    },
+};
+
+/// Pads the spatial axes of a channel-last rank `N` input with zeros
+pub struct ZeroPadding<T, B, const N: usize> {
+    input_shape: Shape<N>,
+    output_shape: Shape<N>,
+    padding: [(usize, usize); N],
+    _marker: PhantomData<(T, B)>,
+}
+
+pub type ZeroPadding1D<T, B> = ZeroPadding<T, B, 2>;
+pub type ZeroPadding2D<T, B> = ZeroPadding<T, B, 3>;
+pub type ZeroPadding3D<T, B> = ZeroPadding<T, B, 4>;
+
+impl<T: Number, B: BackendProvider, const N: usize> ZeroPadding<T, B, N> {
+    pub(crate) fn new(input_shape: Shape<N>, padding: [(usize, usize); N]) -> Self {
+        Self {
+            output_shape: Shape::new(array::from_fn(|i| padding[i].0 + input_shape[i] + padding[i].1)),
+            input_shape,
+            padding,
+            _marker: PhantomData,
+        }
+    }
+
+    fn output_index(&self, ii: [usize; N]) -> [usize; N] {
+        array::from_fn(|i| ii[i] + self.padding[i].0)
+    }
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> Layer for ZeroPadding<T, B, N> {
+    type Input = Tensor<T, B, N>;
+    type ReverseInput = Tensor<T, B, N>;
+    type Internal = [Void; 0];
+    type Output = Tensor<T, B, N>;
+    type ReverseOutput = Tensor<T, B, N>;
+
+    type Computation<'s> = impl FnOnce(
+        Self::ReverseOutput
+    ) -> (
+        Self::ReverseInput,
+        Self::Internal,
+    ) + 's where Self: 's;
+
+    fn input_shapes(&self) -> <<Self::Input as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.input_shape
+    }
+
+    fn output_shapes(&self) -> <<Self::Output as Package>::Shapes as FromRef>::Ref<'_> {
+        &self.output_shape
+    }
+
+    fn feed_forward(&self, input: Self::Input) -> Self::Output {
+        assert_eq!(input.shape(), &self.input_shape);
+
+        let mut output = Tensor::new(T::zero(), self.output_shape.clone());
+        iter_through_shape(&self.input_shape, |ii| output[self.output_index(ii)] = input[ii]);
+        output
+    }
+
+    fn back_propagate(&self, input: Self::Input) -> (Self::Output, Self::Computation<'_>) {
+        (
+            self.feed_forward(input),
+            move |output_d| {
+                assert_eq!(output_d.shape(), &self.output_shape);
+                let mut input_d = Tensor::new(T::zero(), self.input_shape.clone());
+                iter_through_shape(&self.input_shape, |ii| input_d[ii] = output_d[self.output_index(ii)]);
+                (input_d, [])
+            }
+        )
+    }
+
+    fn update(&mut self, []: &Self::Internal) {}
+}
+
+builder::builder! {
+    pub struct Builder<(T), (B), const N: usize> {
+        padding: P,
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    P: IntoPadding<1>,
+> LayerBuilder for Builder<P, T, B, usizeContainer<1>> {
+    type Layer = ZeroPadding1D<T, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let [p0] = resolve_unit_kernel(self.padding.into_padding(), &[input_shape[0]]);
+        Self::Layer::new(input_shape, [p0, (0, 0)])
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    P: IntoPadding<2>,
+> LayerBuilder for Builder<P, T, B, usizeContainer<2>> {
+    type Layer = ZeroPadding2D<T, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let [p0, p1] = resolve_unit_kernel(self.padding.into_padding(), &[input_shape[0], input_shape[1]]);
+        Self::Layer::new(input_shape, [p0, p1, (0, 0)])
+    }
+}
+
+impl<
+    T: Number,
+    B: BackendProvider,
+    P: IntoPadding<3>,
+> LayerBuilder for Builder<P, T, B, usizeContainer<3>> {
+    type Layer = ZeroPadding3D<T, B>;
+
+    fn build(self, input_shape: <<Self::Layer as Layer>::Input as Package>::Shapes) -> Self::Layer {
+        let [p0, p1, p2] = resolve_unit_kernel(self.padding.into_padding(), &[input_shape[0], input_shape[1], input_shape[2]]);
+        Self::Layer::new(input_shape, [p0, p1, p2, (0, 0)])
+    }
+}
@@ -224,18 +224,38 @@ pub mod regularizers;
 pub mod schedules;
 pub mod trainer;
 
+#[cfg(test)]
+mod testing;
+
 #[doc(hidden)]
 pub mod prelude {
     pub use crate::{
         activations::{
+            capped_relu::CappedReLU,
+            celu::CELU,
             elu::ELU,
             exp::EXP,
+            gelu::GELU,
+            hard_sigmoid::HardSigmoid,
+            hard_swish::HardSwish,
             sigmoid::Sigmoid,
             identity::Identity,
+            leaky_relu::LeakyReLU,
             linear::Linear,
+            log_softmax::LogSoftMax,
+            maxout::MaxOut,
+            mish::Mish,
+            parametric::{
+                elu::PELU,
+                prelu::PReLU,
+                swish::PSwish,
+            },
             relu::ReLU,
+            selu::SELU,
+            softmax::SoftMax,
             softplus::SoftPlus,
             softsign::SoftSign,
+            sparsemax::SparseMax,
             swish::Swish,
             tanh::Tanh,
         },
@@ -258,13 +278,40 @@ pub mod prelude {
             pooling::PoolingType,
         },
         losses::{
+            binary_crossentropy::BinaryCrossentropy,
+            categorical_crossentropy::CategoricalCrossentropy,
+            categorical_hinge::CategoricalHinge,
+            contrastive::ContrastiveLoss,
+            cosine_similarity::CosineSimilarity,
+            ctc::CTCLoss,
+            focal::FocalLoss,
+            hinge::Hinge,
+            huber::Huber,
+            info_nce::InfoNCE,
+            kl_divergence::KLDivergence,
+            log_cosh::LogCosh,
+            mean_absolute_error::MeanAbsoluteError,
+            mean_absolute_percentage_error::MeanAbsolutePercentageError,
+            mean_squared_logarithmic_error::MeanSquaredLogarithmicError,
+            poisson::Poisson,
+            scaled::{IntoScaled, Scaled},
+            sparse_categorical_crossentropy::SparseCategoricalCrossentropy,
             square::Square,
+            squared_hinge::SquaredHinge,
+            triplet::TripletLoss,
+            weighted::{ClassWeights, Weighted, Weighting},
         },
         model::{
             Model,
             model_tuple::ModelTuple, // todo: remove
         },
-        metrics::{},
+        metrics::{
+            Metric,
+            CombinedMetric,
+            accuracy::{Accuracy, BinaryAccuracy, SparseCategoricalAccuracy, TopKAccuracy},
+            auc::{AUC, Curve, ExpectedCalibrationError, PrecisionAtRecall, RecallAtPrecision},
+            confusion_matrix::{Average, ConfusionMatrix, F1Score, Precision, Recall},
+        },
         optimizers::{
             adadelta::*,
             adagrad::*,
@@ -0,0 +1,53 @@
+use num_traits::Float;
+
+use crate::losses::{clip, Pointwise};
+
+/// Cross-entropy between independent Bernoulli predictions and targets in `[0, 1]`
+///
+/// With `from_logits` the prediction is treated as a logit and the sigmoid is fused into the loss.
+/// `label_smoothing` pulls the targets towards `0.5`.
+pub struct BinaryCrossentropy {
+    from_logits: bool,
+    label_smoothing: f64,
+}
+
+impl BinaryCrossentropy {
+    pub const fn new(from_logits: bool, label_smoothing: f64) -> Self {
+        Self {
+            from_logits,
+            label_smoothing,
+        }
+    }
+
+    fn smooth<T: Float + From<f64>>(&self, expected: T) -> T {
+        let label_smoothing = T::from(self.label_smoothing);
+        expected * (T::one() - label_smoothing) + label_smoothing / T::from(2.)
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>> Pointwise<T> for BinaryCrossentropy {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        let expected = self.smooth(expected);
+        let loss = if self.from_logits {
+            // max(z, 0) - z * y + ln(1 + exp(-|z|))
+            let relu = if predicted > T::zero() { predicted } else { T::zero() };
+            relu - predicted * expected + (T::one() + (-predicted.abs()).exp()).ln()
+        } else {
+            let p = clip(predicted);
+            -(expected * p.ln() + (T::one() - expected) * (T::one() - p).ln())
+        };
+        loss.into()
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        let expected = self.smooth(expected);
+        if self.from_logits {
+            T::one() / (T::one() + (-predicted).exp()) - expected
+        } else {
+            let p = clip(predicted);
+            (p - expected) / (p * (T::one() - p))
+        }
+    }
+}
+
+impl_pointwise!(BinaryCrossentropy);
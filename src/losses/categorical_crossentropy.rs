@@ -0,0 +1,57 @@
+use num_traits::Float;
+
+use crate::losses::{clip, log_sum_exp, Rowwise};
+
+/// Cross-entropy between a probability distribution over the last axis and a one-hot (or soft) target
+///
+/// With `from_logits` the prediction is treated as unnormalized logits and the softmax is fused into the loss.
+/// `label_smoothing` mixes the target with the uniform distribution.
+pub struct CategoricalCrossentropy {
+    from_logits: bool,
+    label_smoothing: f64,
+}
+
+impl CategoricalCrossentropy {
+    pub const fn new(from_logits: bool, label_smoothing: f64) -> Self {
+        Self {
+            from_logits,
+            label_smoothing,
+        }
+    }
+
+    fn smooth<T: Float + From<f64>>(&self, expected: T, classes: usize) -> T {
+        let label_smoothing = T::from(self.label_smoothing);
+        expected * (T::one() - label_smoothing) + label_smoothing / T::from(classes as f64)
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>> Rowwise<T> for CategoricalCrossentropy {
+    fn loss_row(&self, predicted: &[T], expected: &[T]) -> f64 {
+        let log_sum = if self.from_logits { log_sum_exp(predicted.iter().copied()) } else { T::zero() };
+        predicted.iter()
+            .zip(expected)
+            .map(|(&p, &e)| {
+                let log_p = if self.from_logits { p - log_sum } else { clip(p).ln() };
+                let loss: f64 = (-self.smooth(e, predicted.len()) * log_p).into();
+                loss
+            })
+            .sum()
+    }
+
+    fn derive_row(&self, predicted: &[T], expected: &[T], derivative: &mut [T]) {
+        let classes = predicted.len();
+        if self.from_logits {
+            let log_sum = log_sum_exp(predicted.iter().copied());
+            let total = expected.iter().map(|&e| self.smooth(e, classes)).sum::<T>();
+            derivative.iter_mut()
+                .zip(predicted.iter().zip(expected))
+                .for_each(|(d, (&p, &e))| *d = (p - log_sum).exp() * total - self.smooth(e, classes));
+        } else {
+            derivative.iter_mut()
+                .zip(predicted.iter().zip(expected))
+                .for_each(|(d, (&p, &e))| *d = -self.smooth(e, classes) / clip(p));
+        }
+    }
+}
+
+impl_rowwise!(CategoricalCrossentropy);
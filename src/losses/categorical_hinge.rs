@@ -0,0 +1,51 @@
+use num_traits::Number;
+
+use crate::losses::Rowwise;
+
+/// `max(0, 1 + max_wrong - correct)` over the last axis for one-hot targets,
+/// where `correct` is the score of the target class and `max_wrong` the highest score of any other class
+pub struct CategoricalHinge {
+    _private: (),
+}
+
+impl CategoricalHinge {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Margin of the row together with the index of the highest scoring wrong class
+    fn margin<T: Number>(predicted: &[T], expected: &[T]) -> (T, Option<usize>) {
+        let correct = predicted.iter().zip(expected).fold(T::zero(), |s, (&p, &e)| s + p * e);
+        let wrong = predicted.iter()
+            .zip(expected)
+            .map(|(&p, &e)| (T::one() - e) * p)
+            .enumerate()
+            .fold(None, |max: Option<(usize, T)>, (i, w)| match max {
+                Some((_, m)) if m >= w => max,
+                _ => Some((i, w)),
+            });
+        match wrong {
+            Some((i, w)) => (T::one() + w - correct, Some(i)),
+            None => (T::one() - correct, None),
+        }
+    }
+}
+
+impl<T: Number + Into<f64>> Rowwise<T> for CategoricalHinge {
+    fn loss_row(&self, predicted: &[T], expected: &[T]) -> f64 {
+        let (margin, _) = Self::margin(predicted, expected);
+        if margin > T::zero() { margin.into() } else { 0. }
+    }
+
+    fn derive_row(&self, predicted: &[T], expected: &[T], derivative: &mut [T]) {
+        let (margin, wrong) = Self::margin(predicted, expected);
+        if margin > T::zero() {
+            derivative.iter_mut().zip(expected).for_each(|(d, &e)| *d = T::zero() - e);
+            if let Some(i) = wrong {
+                derivative[i] += T::one() - expected[i];
+            }
+        }
+    }
+}
+
+impl_rowwise!(CategoricalHinge);
@@ -0,0 +1,104 @@
+use num_traits::Float;
+use tensor::{BackendProvider, Tensor};
+
+use crate::losses::{scaled::IntoScaled, Loss, EPSILON};
+
+/// Loss on pairs of embeddings of shape `[batch, dimension]`
+///
+/// The expected value holds one label per pair, `1` for similar and `0` for dissimilar pairs.
+/// Similar pairs are pulled together by their squared distance,
+/// dissimilar pairs are pushed apart until their distance reaches `margin`.
+pub struct ContrastiveLoss {
+    margin: f64,
+}
+
+impl ContrastiveLoss {
+    pub const fn new(margin: f64) -> Self {
+        Self { margin }
+    }
+
+    fn distance<T: Float, B: BackendProvider>(a: &Tensor<T, B, 2>, b: &Tensor<T, B, 2>, i: usize) -> T {
+        (0..a.shape()[1]).map(|k| (a[[i, k]] - b[[i, k]]) * (a[[i, k]] - b[[i, k]])).sum::<T>().sqrt()
+    }
+
+    fn pair_loss<T: Float + From<f64> + Into<f64>, B: BackendProvider>(
+        &self,
+        a: &Tensor<T, B, 2>,
+        b: &Tensor<T, B, 2>,
+        labels: &Tensor<T, B, 1>,
+    ) -> f64 {
+        assert_eq!(a.shape(), b.shape());
+        assert_eq!(a.shape()[0], labels.shape()[0]);
+        let margin = T::from(self.margin);
+        (0..a.shape()[0])
+            .map(|i| {
+                let d = Self::distance(a, b, i);
+                let push = if margin > d { margin - d } else { T::zero() };
+                let loss: f64 = (labels[i] * d * d + (T::one() - labels[i]) * push * push).into();
+                loss
+            })
+            .sum()
+    }
+
+    fn pair_derive<T: Float + From<f64>, B: BackendProvider>(
+        &self,
+        a: &Tensor<T, B, 2>,
+        b: &Tensor<T, B, 2>,
+        labels: &Tensor<T, B, 1>,
+    ) -> (Tensor<T, B, 2>, Tensor<T, B, 2>) {
+        assert_eq!(a.shape(), b.shape());
+        assert_eq!(a.shape()[0], labels.shape()[0]);
+        let margin = T::from(self.margin);
+        let two = T::from(2.);
+        let mut a_d = Tensor::new(T::zero(), a.shape().clone());
+        let mut b_d = Tensor::new(T::zero(), b.shape().clone());
+        for i in 0..a.shape()[0] {
+            let d = Self::distance(a, b, i);
+            // derivative with respect to `a - b`, divided by `a - b`
+            let pull = two * labels[i];
+            let push = if margin > d {
+                let d = if d < T::from(EPSILON) { T::from(EPSILON) } else { d };
+                two * (T::one() - labels[i]) * (margin - d) / d
+            } else {
+                T::zero()
+            };
+            for k in 0..a.shape()[1] {
+                let g = (pull - push) * (a[[i, k]] - b[[i, k]]);
+                a_d[[i, k]] = g;
+                b_d[[i, k]] = -g;
+            }
+        }
+        (a_d, b_d)
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>, B: BackendProvider> Loss<
+    (Tensor<T, B, 2>, Tensor<T, B, 2>),
+    (Tensor<T, B, 2>, Tensor<T, B, 2>),
+    Tensor<T, B, 1>,
+> for ContrastiveLoss {
+    fn loss(&self, (a, b): &(Tensor<T, B, 2>, Tensor<T, B, 2>), expected: &Tensor<T, B, 1>) -> f64 {
+        self.pair_loss(a, b, expected)
+    }
+
+    fn derive(&self, (a, b): &(Tensor<T, B, 2>, Tensor<T, B, 2>), expected: &Tensor<T, B, 1>) -> (Tensor<T, B, 2>, Tensor<T, B, 2>) {
+        self.pair_derive(a, b, expected)
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>, B: BackendProvider> Loss<
+    [Tensor<T, B, 2>; 2],
+    [Tensor<T, B, 2>; 2],
+    Tensor<T, B, 1>,
+> for ContrastiveLoss {
+    fn loss(&self, [a, b]: &[Tensor<T, B, 2>; 2], expected: &Tensor<T, B, 1>) -> f64 {
+        self.pair_loss(a, b, expected)
+    }
+
+    fn derive(&self, [a, b]: &[Tensor<T, B, 2>; 2], expected: &Tensor<T, B, 1>) -> [Tensor<T, B, 2>; 2] {
+        let (a, b) = self.pair_derive(a, b, expected);
+        [a, b]
+    }
+}
+
+impl IntoScaled for ContrastiveLoss {}
@@ -0,0 +1,44 @@
+use num_traits::Float;
+
+use crate::losses::{Rowwise, EPSILON};
+
+/// Negative cosine similarity between the prediction and the target along the last axis
+pub struct CosineSimilarity {
+    _private: (),
+}
+
+impl CosineSimilarity {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+
+    fn norm<T: Float + From<f64>>(x: &[T]) -> T {
+        let norm = x.iter().map(|&x| x * x).sum::<T>().sqrt();
+        let epsilon = T::from(EPSILON);
+        if norm < epsilon { epsilon } else { norm }
+    }
+
+    fn cosine<T: Float + From<f64>>(predicted: &[T], expected: &[T]) -> T {
+        let dot = predicted.iter().zip(expected).map(|(&p, &e)| p * e).sum::<T>();
+        dot / (Self::norm(predicted) * Self::norm(expected))
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>> Rowwise<T> for CosineSimilarity {
+    fn loss_row(&self, predicted: &[T], expected: &[T]) -> f64 {
+        (-Self::cosine(predicted, expected)).into()
+    }
+
+    fn derive_row(&self, predicted: &[T], expected: &[T], derivative: &mut [T]) {
+        let cosine = Self::cosine(predicted, expected);
+        let predicted_norm = Self::norm(predicted);
+        let expected_norm = Self::norm(expected);
+        derivative.iter_mut()
+            .zip(predicted.iter().zip(expected))
+            .for_each(|(d, (&p, &e))| {
+                *d = cosine * p / (predicted_norm * predicted_norm) - e / (predicted_norm * expected_norm)
+            });
+    }
+}
+
+impl_rowwise!(CosineSimilarity);
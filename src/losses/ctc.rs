@@ -0,0 +1,264 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+};
+
+use num_traits::Float;
+use tensor::{BackendProvider, Tensor};
+
+use crate::losses::{scaled::IntoScaled, Loss};
+
+/// Connectionist Temporal Classification loss over `[time, classes]` logits or log-probabilities
+///
+/// The expected value is the label sequence, which may be of any length up to the number of timesteps.
+/// The prediction is normalized with a log-softmax over the classes of every timestep,
+/// so the derivative is with respect to the logits.
+///
+/// A label sequence cannot be aligned with the prediction when it needs more timesteps than there are,
+/// counting a blank between every pair of repeated labels.
+/// Such sequences panic, unless `zero_infinity` is set, in which case their loss and derivative are zero.
+pub struct CTCLoss {
+    blank: usize,
+    zero_infinity: bool,
+}
+
+/// `ln(exp(a) + exp(b))`
+fn log_add(a: f64, b: f64) -> f64 {
+    if a == f64::NEG_INFINITY {
+        return b;
+    }
+    if b == f64::NEG_INFINITY {
+        return a;
+    }
+    let (max, min) = if a > b { (a, b) } else { (b, a) };
+    max + (min - max).exp().ln_1p()
+}
+
+impl CTCLoss {
+    pub const fn new(blank: usize, zero_infinity: bool) -> Self {
+        Self { blank, zero_infinity }
+    }
+
+    /// Log-likelihood of the label sequence, `None` for ignored sequences that cannot be aligned
+    fn check_alignable(&self, likelihood: f64, time: usize, labels: usize) -> Option<f64> {
+        if likelihood != f64::NEG_INFINITY {
+            return Some(likelihood);
+        }
+        assert!(
+            self.zero_infinity,
+            "a label sequence of length {labels} cannot be aligned with {time} timesteps, set `zero_infinity` to ignore it",
+        );
+        None
+    }
+
+    /// Log-softmax of every timestep
+    fn log_probabilities<T: Float + Into<f64>, B: BackendProvider>(predicted: &Tensor<T, B, 2>) -> Vec<Vec<f64>> {
+        (0..predicted.shape()[0])
+            .map(|t| {
+                let row = (0..predicted.shape()[1]).map(|k| predicted[[t, k]].into()).collect::<Vec<f64>>();
+                let log_sum = row.iter().copied().fold(f64::NEG_INFINITY, log_add);
+                row.into_iter().map(|z| z - log_sum).collect()
+            })
+            .collect()
+    }
+
+    /// Label sequence with blanks inserted before, between and after the labels
+    fn extend<B: BackendProvider>(&self, labels: &Tensor<usize, B, 1>) -> Vec<usize> {
+        let mut extended = vec![self.blank];
+        labels.iter().for_each(|&l| {
+            assert_ne!(l, self.blank);
+            extended.push(l);
+            extended.push(self.blank);
+        });
+        extended
+    }
+
+    /// Whether the path may skip the blank in front of position `s`
+    fn can_skip(extended: &[usize], s: usize) -> bool {
+        s >= 2 && extended[s] != extended[s - 2]
+    }
+
+    /// Forward and backward variables, both including the emission of their own timestep,
+    /// together with the log-likelihood of the label sequence
+    fn forward_backward(&self, log_probabilities: &[Vec<f64>], extended: &[usize]) -> (Vec<Vec<f64>>, Vec<Vec<f64>>, f64) {
+        let (time, length) = (log_probabilities.len(), extended.len());
+        let mut alpha = vec![vec![f64::NEG_INFINITY; length]; time];
+        let mut beta = vec![vec![f64::NEG_INFINITY; length]; time];
+        if time == 0 {
+            let likelihood = if length == 1 { 0. } else { f64::NEG_INFINITY };
+            return (alpha, beta, likelihood);
+        }
+
+        alpha[0][0] = log_probabilities[0][extended[0]];
+        if length > 1 {
+            alpha[0][1] = log_probabilities[0][extended[1]];
+        }
+        for t in 1..time {
+            for s in 0..length {
+                let mut a = alpha[t - 1][s];
+                if s >= 1 {
+                    a = log_add(a, alpha[t - 1][s - 1]);
+                }
+                if Self::can_skip(extended, s) {
+                    a = log_add(a, alpha[t - 1][s - 2]);
+                }
+                alpha[t][s] = a + log_probabilities[t][extended[s]];
+            }
+        }
+
+        beta[time - 1][length - 1] = log_probabilities[time - 1][extended[length - 1]];
+        if length > 1 {
+            beta[time - 1][length - 2] = log_probabilities[time - 1][extended[length - 2]];
+        }
+        for t in (0..time - 1).rev() {
+            for s in 0..length {
+                let mut b = beta[t + 1][s];
+                if s + 1 < length {
+                    b = log_add(b, beta[t + 1][s + 1]);
+                }
+                if s + 2 < length && Self::can_skip(extended, s + 2) {
+                    b = log_add(b, beta[t + 1][s + 2]);
+                }
+                beta[t][s] = b + log_probabilities[t][extended[s]];
+            }
+        }
+
+        let mut likelihood = alpha[time - 1][length - 1];
+        if length > 1 {
+            likelihood = log_add(likelihood, alpha[time - 1][length - 2]);
+        }
+        (alpha, beta, likelihood)
+    }
+
+    /// Most probable class of every timestep with repeats collapsed and blanks removed
+    pub fn greedy_decode<T: Float, B: BackendProvider>(&self, predicted: &Tensor<T, B, 2>) -> Vec<usize> {
+        let mut decoded = Vec::new();
+        let mut previous = None;
+        for t in 0..predicted.shape()[0] {
+            let best = (0..predicted.shape()[1])
+                .max_by(|&a, &b| predicted[[t, a]].partial_cmp(&predicted[[t, b]]).unwrap_or(Ordering::Equal));
+            if best != previous {
+                if let Some(best) = best.filter(|&best| best != self.blank) {
+                    decoded.push(best);
+                }
+            }
+            previous = best;
+        }
+        decoded
+    }
+
+    /// Prefix beam search keeping the `beam_width` most probable label sequences at every timestep
+    pub fn beam_search_decode<T: Float + Into<f64>, B: BackendProvider>(&self, predicted: &Tensor<T, B, 2>, beam_width: usize) -> Vec<usize> {
+        assert!(beam_width > 0);
+        let total = |&(blank, non_blank): &(f64, f64)| log_add(blank, non_blank);
+        // log-probabilities of every prefix ending in a blank and ending in a label
+        let mut beams = vec![(Vec::new(), (0., f64::NEG_INFINITY))];
+        for row in Self::log_probabilities(predicted) {
+            let mut next = HashMap::<Vec<usize>, (f64, f64)>::new();
+            for (prefix, probabilities) in &beams {
+                let (blank, non_blank) = *probabilities;
+                let prefix_total = total(probabilities);
+                for (k, &p) in row.iter().enumerate() {
+                    if k == self.blank {
+                        let entry = next.entry(prefix.clone()).or_insert((f64::NEG_INFINITY, f64::NEG_INFINITY));
+                        entry.0 = log_add(entry.0, prefix_total + p);
+                        continue;
+                    }
+                    let mut extended = prefix.clone();
+                    extended.push(k);
+                    if prefix.last() == Some(&k) {
+                        // a repeated label needs a blank in between, otherwise it collapses into the prefix
+                        let entry = next.entry(extended).or_insert((f64::NEG_INFINITY, f64::NEG_INFINITY));
+                        entry.1 = log_add(entry.1, blank + p);
+                        let entry = next.entry(prefix.clone()).or_insert((f64::NEG_INFINITY, f64::NEG_INFINITY));
+                        entry.1 = log_add(entry.1, non_blank + p);
+                    } else {
+                        let entry = next.entry(extended).or_insert((f64::NEG_INFINITY, f64::NEG_INFINITY));
+                        entry.1 = log_add(entry.1, prefix_total + p);
+                    }
+                }
+            }
+            beams = next.into_iter().collect();
+            beams.sort_by(|a, b| total(&b.1).partial_cmp(&total(&a.1)).unwrap_or(Ordering::Equal));
+            beams.truncate(beam_width);
+        }
+        beams.into_iter().next().map(|(prefix, _)| prefix).unwrap_or_default()
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>, B: BackendProvider> Loss<Tensor<T, B, 2>, Tensor<T, B, 2>, Tensor<usize, B, 1>> for CTCLoss {
+    fn loss(&self, predicted: &Tensor<T, B, 2>, expected: &Tensor<usize, B, 1>) -> f64 {
+        let log_probabilities = Self::log_probabilities(predicted);
+        let (_, _, likelihood) = self.forward_backward(&log_probabilities, &self.extend(expected));
+        self.check_alignable(likelihood, predicted.shape()[0], expected.shape()[0]).map_or(0., |likelihood| -likelihood)
+    }
+
+    fn derive(&self, predicted: &Tensor<T, B, 2>, expected: &Tensor<usize, B, 1>) -> Tensor<T, B, 2> {
+        let log_probabilities = Self::log_probabilities(predicted);
+        let extended = self.extend(expected);
+        let (alpha, beta, likelihood) = self.forward_backward(&log_probabilities, &extended);
+        let mut derivative = Tensor::new(T::zero(), predicted.shape().clone());
+        let Some(likelihood) = self.check_alignable(likelihood, predicted.shape()[0], expected.shape()[0]) else {
+            return derivative;
+        };
+        for (t, row) in log_probabilities.iter().enumerate() {
+            // log of the posterior probability of emitting every class at timestep `t`
+            let mut occupancy = vec![f64::NEG_INFINITY; row.len()];
+            for (s, &k) in extended.iter().enumerate() {
+                occupancy[k] = log_add(occupancy[k], alpha[t][s] + beta[t][s] - row[k]);
+            }
+            for (k, &p) in row.iter().enumerate() {
+                derivative[[t, k]] = T::from(p.exp() - (occupancy[k] - likelihood).exp());
+            }
+        }
+        derivative
+    }
+}
+
+impl IntoScaled for CTCLoss {}
+
+#[cfg(test)]
+mod tests {
+    use tensor::{Shape, Tensor, VecProvider};
+
+    use crate::{
+        losses::Loss,
+        testing::{assert_close, assert_gradient, numeric_gradient, tensor, values},
+    };
+    use super::CTCLoss;
+
+    fn labels(labels: &[usize]) -> Tensor<usize, VecProvider, 1> {
+        Shape::new([labels.len()]).into_tensor(|i| labels[i])
+    }
+
+    #[test]
+    fn sums_over_every_alignment() {
+        let loss = CTCLoss::new(0, false);
+        // "1", "-1" and "1-" out of four equally likely paths
+        assert_close(loss.loss(&tensor([2, 2], &[0.; 4]), &labels(&[1])), -0.75f64.ln());
+    }
+
+    #[test]
+    fn gradient() {
+        let loss = CTCLoss::new(0, false);
+        let predicted = tensor([5, 4], &values(20, 1));
+        let expected = labels(&[1, 2, 2]);
+        let numeric = numeric_gradient(&predicted, |p| loss.loss(p, &expected));
+        assert_gradient(&loss.derive(&predicted, &expected), &numeric);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be aligned")]
+    fn unalignable_labels_panic() {
+        // the repeated label needs a blank in between, so three timesteps
+        CTCLoss::new(0, false).loss(&tensor([2, 3], &values(6, 2)), &labels(&[1, 1]));
+    }
+
+    #[test]
+    fn zero_infinity_ignores_unalignable_labels() {
+        let loss = CTCLoss::new(0, true);
+        let predicted = tensor([2, 3], &values(6, 2));
+        assert_eq!(loss.loss(&predicted, &labels(&[1, 1])), 0.);
+        assert!(loss.derive(&predicted, &labels(&[1, 1])).iter().all(|&d| d == 0.));
+    }
+}
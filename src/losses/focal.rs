@@ -0,0 +1,76 @@
+use num_traits::Float;
+
+use crate::losses::{clip, Pointwise};
+
+/// [`BinaryCrossentropy`] scaled by `(1 - p_t)^gamma`, which down-weights well classified examples,
+/// and weighted by `alpha` for the positive and `1 - alpha` for the negative class
+///
+/// `p_t` is the predicted probability of the expected class.
+///
+/// [`BinaryCrossentropy`]: crate::losses::binary_crossentropy::BinaryCrossentropy
+pub struct FocalLoss {
+    gamma: f64,
+    alpha: f64,
+}
+
+impl FocalLoss {
+    pub const fn new(gamma: f64, alpha: f64) -> Self {
+        Self { gamma, alpha }
+    }
+
+    /// `p_t`, `alpha_t` and `(1 - p_t)^gamma` of one entry
+    fn terms<T: Float + From<f64>>(&self, predicted: T, expected: T) -> (T, T, T) {
+        let p = clip(predicted);
+        let alpha = T::from(self.alpha);
+        let p_t = expected * p + (T::one() - expected) * (T::one() - p);
+        let alpha_t = expected * alpha + (T::one() - expected) * (T::one() - alpha);
+        let modulation = (T::from(self.gamma) * clip(T::one() - p_t).ln()).exp();
+        (p_t, alpha_t, modulation)
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>> Pointwise<T> for FocalLoss {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        let (p_t, alpha_t, modulation) = self.terms(predicted, expected);
+        (-alpha_t * modulation * clip(p_t).ln()).into()
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        let (p_t, alpha_t, modulation) = self.terms(predicted, expected);
+        let p_t = clip(p_t);
+        let gamma = T::from(self.gamma);
+        // d/dp_t of -(1 - p_t)^gamma * ln(p_t)
+        let p_t_d = gamma * modulation / clip(T::one() - p_t) * p_t.ln() - modulation / p_t;
+        // dp_t/dp = 2 * expected - 1
+        alpha_t * p_t_d * (expected + expected - T::one())
+    }
+}
+
+impl_pointwise!(FocalLoss);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        losses::{binary_crossentropy::BinaryCrossentropy, Loss},
+        testing::{assert_close, assert_gradient, numeric_gradient, tensor},
+    };
+    use super::FocalLoss;
+
+    #[test]
+    fn zero_gamma_is_weighted_binary_crossentropy() {
+        let predicted = tensor([4], &[0.1, 0.4, 0.7, 0.95]);
+        let expected = tensor([4], &[0., 1., 1., 0.]);
+        let focal = FocalLoss::new(0., 0.5).loss(&predicted, &expected);
+        let crossentropy = BinaryCrossentropy::new(false, 0.).loss(&predicted, &expected);
+        assert_close(focal, 0.5 * crossentropy);
+    }
+
+    #[test]
+    fn gradient() {
+        let loss = FocalLoss::new(2., 0.25);
+        let predicted = tensor([2, 3], &[0.1, 0.4, 0.7, 0.95, 0.3, 0.55]);
+        let expected = tensor([2, 3], &[0., 1., 1., 0., 0., 1.]);
+        let numeric = numeric_gradient(&predicted, |p| loss.loss(p, &expected));
+        assert_gradient(&loss.derive(&predicted, &expected), &numeric);
+    }
+}
@@ -0,0 +1,31 @@
+use num_traits::Number;
+
+use crate::losses::Pointwise;
+
+/// `max(0, 1 - expected * predicted)` for targets in `{-1, 1}`
+pub struct Hinge {
+    _private: (),
+}
+
+impl Hinge {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl<T: Number + Into<f64>> Pointwise<T> for Hinge {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        let margin = T::one() - expected * predicted;
+        if margin > T::zero() { margin.into() } else { 0. }
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        if T::one() - expected * predicted > T::zero() {
+            T::zero() - expected
+        } else {
+            T::zero()
+        }
+    }
+}
+
+impl_pointwise!(Hinge);
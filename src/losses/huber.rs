@@ -0,0 +1,40 @@
+use num_traits::Float;
+
+use crate::losses::Pointwise;
+
+/// Quadratic for errors smaller than `delta` and linear beyond it
+pub struct Huber {
+    delta: f64,
+}
+
+impl Huber {
+    pub const fn new(delta: f64) -> Self {
+        Self { delta }
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>> Pointwise<T> for Huber {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        let delta = T::from(self.delta);
+        let half = T::from(0.5);
+        let d = (predicted - expected).abs();
+        let loss = if d <= delta {
+            half * d * d
+        } else {
+            delta * (d - half * delta)
+        };
+        loss.into()
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        let delta = T::from(self.delta);
+        let d = predicted - expected;
+        if d.abs() <= delta {
+            d
+        } else {
+            delta * d.signum()
+        }
+    }
+}
+
+impl_pointwise!(Huber);
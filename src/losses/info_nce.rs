@@ -0,0 +1,157 @@
+use num_traits::Float;
+use tensor::{BackendProvider, Tensor};
+use void::Void;
+
+use crate::losses::{log_sum_exp, scaled::IntoScaled, Loss, EPSILON};
+
+/// In-batch contrastive loss (NT-Xent) on anchor and positive embeddings of shape `[batch, dimension]`
+///
+/// Row `i` of the positives is the positive for row `i` of the anchors and every other row is a negative.
+/// The logits are the cosine similarities divided by `temperature`.
+pub struct InfoNCE {
+    temperature: f64,
+}
+
+impl InfoNCE {
+    pub const fn new(temperature: f64) -> Self {
+        Self { temperature }
+    }
+
+    /// L2-normalized rows together with their norms
+    fn normalize<T: Float + From<f64>, B: BackendProvider>(x: &Tensor<T, B, 2>) -> (Tensor<T, B, 2>, Vec<T>) {
+        let epsilon = T::from(EPSILON);
+        let norms = (0..x.shape()[0])
+            .map(|i| (0..x.shape()[1]).map(|k| x[[i, k]] * x[[i, k]]).sum::<T>().sqrt())
+            .map(|n| if n < epsilon { epsilon } else { n })
+            .collect::<Vec<_>>();
+        let columns = x.shape()[1];
+        (x.shape().clone().into_tensor(|i| x[i] / norms[i / columns]), norms)
+    }
+
+    fn logits<T: Float + From<f64>, B: BackendProvider>(&self, u: &Tensor<T, B, 2>, v: &Tensor<T, B, 2>) -> Vec<Vec<T>> {
+        let temperature = T::from(self.temperature);
+        (0..u.shape()[0])
+            .map(|i| (0..v.shape()[0])
+                .map(|j| (0..u.shape()[1]).map(|k| u[[i, k]] * v[[j, k]]).sum::<T>() / temperature)
+                .collect())
+            .collect()
+    }
+
+    fn pair_loss<T: Float + From<f64> + Into<f64>, B: BackendProvider>(&self, anchors: &Tensor<T, B, 2>, positives: &Tensor<T, B, 2>) -> f64 {
+        assert_eq!(anchors.shape(), positives.shape());
+        let (u, _) = Self::normalize(anchors);
+        let (v, _) = Self::normalize(positives);
+        self.logits(&u, &v)
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let loss: f64 = (log_sum_exp(row.iter().copied()) - row[i]).into();
+                loss
+            })
+            .sum()
+    }
+
+    fn pair_derive<T: Float + From<f64>, B: BackendProvider>(
+        &self,
+        anchors: &Tensor<T, B, 2>,
+        positives: &Tensor<T, B, 2>,
+    ) -> (Tensor<T, B, 2>, Tensor<T, B, 2>) {
+        assert_eq!(anchors.shape(), positives.shape());
+        let temperature = T::from(self.temperature);
+        let (batch, dimension) = (anchors.shape()[0], anchors.shape()[1]);
+        let (u, u_norms) = Self::normalize(anchors);
+        let (v, v_norms) = Self::normalize(positives);
+        // derivative with respect to the logits: softmax of the row minus the one-hot positive
+        let gradients = self.logits(&u, &v)
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let log_sum = log_sum_exp(row.iter().copied());
+                row.into_iter()
+                    .enumerate()
+                    .map(|(j, s)| (s - log_sum).exp() - if i == j { T::one() } else { T::zero() })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut u_d = Tensor::new(T::zero(), anchors.shape().clone());
+        let mut v_d = Tensor::new(T::zero(), positives.shape().clone());
+        for i in 0..batch {
+            for j in 0..batch {
+                let g = gradients[i][j] / temperature;
+                for k in 0..dimension {
+                    u_d[[i, k]] += g * v[[j, k]];
+                    v_d[[j, k]] += g * u[[i, k]];
+                }
+            }
+        }
+        (Self::through_normalization(&u, &u_d, &u_norms), Self::through_normalization(&v, &v_d, &v_norms))
+    }
+
+    /// Propagates the derivative of normalized rows `u = x / |x|` back to `x`
+    fn through_normalization<T: Float, B: BackendProvider>(u: &Tensor<T, B, 2>, u_d: &Tensor<T, B, 2>, norms: &[T]) -> Tensor<T, B, 2> {
+        let columns = u.shape()[1];
+        let projections = (0..u.shape()[0])
+            .map(|i| (0..columns).map(|k| u_d[[i, k]] * u[[i, k]]).sum::<T>())
+            .collect::<Vec<_>>();
+        u.shape().clone().into_tensor(|i| (u_d[i] - projections[i / columns] * u[i]) / norms[i / columns])
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>, B: BackendProvider> Loss<
+    (Tensor<T, B, 2>, Tensor<T, B, 2>),
+    (Tensor<T, B, 2>, Tensor<T, B, 2>),
+    [Void; 0],
+> for InfoNCE {
+    fn loss(&self, (anchors, positives): &(Tensor<T, B, 2>, Tensor<T, B, 2>), []: &[Void; 0]) -> f64 {
+        self.pair_loss(anchors, positives)
+    }
+
+    fn derive(&self, (anchors, positives): &(Tensor<T, B, 2>, Tensor<T, B, 2>), []: &[Void; 0]) -> (Tensor<T, B, 2>, Tensor<T, B, 2>) {
+        self.pair_derive(anchors, positives)
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>, B: BackendProvider> Loss<
+    [Tensor<T, B, 2>; 2],
+    [Tensor<T, B, 2>; 2],
+    [Void; 0],
+> for InfoNCE {
+    fn loss(&self, [anchors, positives]: &[Tensor<T, B, 2>; 2], []: &[Void; 0]) -> f64 {
+        self.pair_loss(anchors, positives)
+    }
+
+    fn derive(&self, [anchors, positives]: &[Tensor<T, B, 2>; 2], []: &[Void; 0]) -> [Tensor<T, B, 2>; 2] {
+        let (anchors, positives) = self.pair_derive(anchors, positives);
+        [anchors, positives]
+    }
+}
+
+impl IntoScaled for InfoNCE {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        losses::Loss,
+        testing::{assert_close, assert_gradient, numeric_gradient, tensor, values},
+    };
+    use super::InfoNCE;
+
+    #[test]
+    fn loss_of_orthogonal_pairs() {
+        let x = tensor([2, 2], &[1., 0., 0., 1.]);
+        let loss = InfoNCE::new(1.).loss(&(x.clone(), x), &[]);
+        assert_close(loss, 2. * ((1. + 1f64.exp()).ln() - 1.));
+    }
+
+    #[test]
+    fn gradient() {
+        let loss = InfoNCE::new(0.5);
+        let anchors = tensor([3, 4], &values(12, 1));
+        let positives = tensor([3, 4], &values(12, 2));
+        let anchors_numeric = numeric_gradient(&anchors, |a| loss.loss(&(a.clone(), positives.clone()), &[]));
+        let positives_numeric = numeric_gradient(&positives, |p| loss.loss(&(anchors.clone(), p.clone()), &[]));
+        let (anchors_d, positives_d) = loss.derive(&(anchors, positives), &[]);
+        assert_gradient(&anchors_d, &anchors_numeric);
+        assert_gradient(&positives_d, &positives_numeric);
+    }
+}
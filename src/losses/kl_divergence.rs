@@ -0,0 +1,53 @@
+use num_traits::Float;
+
+use crate::losses::{clip, Pointwise};
+
+/// Kullback-Leibler divergence `expected * ln(expected / predicted)` between probability distributions
+pub struct KLDivergence {
+    _private: (),
+}
+
+impl KLDivergence {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>> Pointwise<T> for KLDivergence {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        let expected = clip(expected);
+        (expected * (expected / clip(predicted)).ln()).into()
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        let p = clip(predicted);
+        // the loss is constant in `predicted` wherever the clip is active
+        if p != predicted {
+            T::zero()
+        } else {
+            -clip(expected) / p
+        }
+    }
+}
+
+impl_pointwise!(KLDivergence);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        losses::Pointwise,
+        testing::assert_close,
+    };
+    use super::KLDivergence;
+
+    #[test]
+    fn derivative_matches_the_clipped_loss() {
+        let loss = KLDivergence::new();
+        for (predicted, expected) in [(0.3, 0.6), (0.9, 0.2), (0., 0.5), (1e-9, 0.5), (1., 0.5)] {
+            let step = 1e-9;
+            let numeric = (Pointwise::<f64>::loss_at(&loss, predicted + step, expected)
+                - Pointwise::<f64>::loss_at(&loss, predicted - step, expected)) / (2. * step);
+            assert_close(Pointwise::<f64>::derive_at(&loss, predicted, expected), numeric);
+        }
+    }
+}
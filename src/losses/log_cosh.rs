@@ -0,0 +1,33 @@
+use num_traits::Float;
+
+use crate::losses::Pointwise;
+
+/// `ln(cosh(predicted - expected))`, which behaves like [`Square`] for small errors and like
+/// [`MeanAbsoluteError`] for large ones
+///
+/// [`Square`]: crate::losses::square::Square
+/// [`MeanAbsoluteError`]: crate::losses::mean_absolute_error::MeanAbsoluteError
+pub struct LogCosh {
+    _private: (),
+}
+
+impl LogCosh {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>> Pointwise<T> for LogCosh {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        // ln(cosh(d)) = |d| + ln(1 + exp(-2|d|)) - ln(2), which does not overflow for large |d|
+        let d = (predicted - expected).abs();
+        let two = T::from(2.);
+        (d + (T::one() + (-two * d).exp()).ln() - two.ln()).into()
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        (predicted - expected).tanh()
+    }
+}
+
+impl_pointwise!(LogCosh);
@@ -0,0 +1,25 @@
+use num_traits::Number;
+
+use crate::losses::Pointwise;
+
+pub struct MeanAbsoluteError {
+    _private: (),
+}
+
+impl MeanAbsoluteError {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl<T: Number + Into<f64>> Pointwise<T> for MeanAbsoluteError {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        (predicted - expected).abs().into()
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        (predicted - expected).signum()
+    }
+}
+
+impl_pointwise!(MeanAbsoluteError);
@@ -0,0 +1,32 @@
+use num_traits::Float;
+
+use crate::losses::{Pointwise, EPSILON};
+
+/// Absolute error in percent of the expected value
+pub struct MeanAbsolutePercentageError {
+    _private: (),
+}
+
+impl MeanAbsolutePercentageError {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+
+    fn scale<T: Float + From<f64>>(expected: T) -> T {
+        let epsilon = T::from(EPSILON);
+        let expected = expected.abs();
+        T::from(100.) / if expected < epsilon { epsilon } else { expected }
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>> Pointwise<T> for MeanAbsolutePercentageError {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        ((predicted - expected).abs() * Self::scale(expected)).into()
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        (predicted - expected).signum() * Self::scale(expected)
+    }
+}
+
+impl_pointwise!(MeanAbsolutePercentageError);
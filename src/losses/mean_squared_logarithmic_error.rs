@@ -0,0 +1,35 @@
+use num_traits::Float;
+
+use crate::losses::Pointwise;
+
+/// Squared difference of `ln(1 + x)`, with negative values clamped to zero
+pub struct MeanSquaredLogarithmicError {
+    _private: (),
+}
+
+impl MeanSquaredLogarithmicError {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+
+    fn log<T: Float>(x: T) -> T {
+        (T::one() + if x < T::zero() { T::zero() } else { x }).ln()
+    }
+}
+
+impl<T: Float + Into<f64>> Pointwise<T> for MeanSquaredLogarithmicError {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        let d = Self::log(predicted) - Self::log(expected);
+        (d * d).into()
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        if predicted < T::zero() {
+            return T::zero();
+        }
+        let d = Self::log(predicted) - Self::log(expected);
+        (d + d) / (T::one() + predicted)
+    }
+}
+
+impl_pointwise!(MeanSquaredLogarithmicError);
@@ -1,8 +1,433 @@
+use std::{
+    array,
+    cmp::Ordering,
+};
+
+use num_traits::{Float, Number};
+use tensor::{BackendProvider, Shape, Tensor};
+use void::Void;
+
+use crate::{
+    data::Masked,
+    layers::fibers,
+    losses::scaled::IntoScaled,
+};
+
+/// Implements [`Loss`] over every [`Zip`] package for [`Pointwise`] losses
+macro_rules! impl_pointwise {
+    ($($loss:ty),* $(,)?) => {
+        $(
+            impl<I: $crate::losses::Zip<Self>> $crate::losses::Loss<I, I> for $loss {
+                fn loss(&self, predicted: &I, expected: &I) -> f64 {
+                    I::loss(self, predicted, expected)
+                }
+
+                fn derive(&self, predicted: &I, expected: &I) -> I {
+                    I::zip(self, predicted, expected)
+                }
+            }
+
+            impl $crate::losses::scaled::IntoScaled for $loss {}
+        )*
+    };
+}
+
+/// Implements [`Loss`] over every [`Rows`] package for [`Rowwise`] losses
+macro_rules! impl_rowwise {
+    ($($loss:ty),* $(,)?) => {
+        $(
+            impl<I: $crate::losses::Rows<Self>> $crate::losses::Loss<I, I> for $loss {
+                fn loss(&self, predicted: &I, expected: &I) -> f64 {
+                    I::loss(self, predicted, expected)
+                }
+
+                fn derive(&self, predicted: &I, expected: &I) -> I {
+                    I::rows(self, predicted, expected)
+                }
+            }
+
+            impl $crate::losses::scaled::IntoScaled for $loss {}
+        )*
+    };
+}
+
+pub mod binary_crossentropy;
+pub mod categorical_crossentropy;
+pub mod categorical_hinge;
+pub mod contrastive;
+pub mod cosine_similarity;
+pub mod ctc;
+pub mod focal;
+pub mod hinge;
+pub mod huber;
+pub mod info_nce;
+pub mod kl_divergence;
+pub mod log_cosh;
+pub mod mean_absolute_error;
+pub mod mean_absolute_percentage_error;
+pub mod mean_squared_logarithmic_error;
+pub mod poisson;
+pub mod scaled;
+pub mod sparse_categorical_crossentropy;
 pub mod square;
+pub mod squared_hinge;
+pub mod triplet;
+pub mod weighted;
 // todo: add losses
 
-pub trait Loss<T, U> {
-    // fn loss(&self, predicted: &C, expected: &C) -> T;
+/// Compares the predicted output of type `T` with the expected value of type `E`
+/// and produces the derivative of type `U` with respect to the prediction
+pub trait Loss<T, U, E = T> {
+    fn loss(&self, predicted: &T, expected: &E) -> f64;
+
+    fn derive(&self, predicted: &T, expected: &E) -> U;
+}
+
+/// Applies every loss of the pair to the matching output and sums their values
+impl<
+    T0,
+    U0,
+    E0,
+    T1,
+    U1,
+    E1,
+    L0: Loss<T0, U0, E0>,
+    L1: Loss<T1, U1, E1>,
+> Loss<(T0, T1), (U0, U1), (E0, E1)> for (L0, L1) {
+    fn loss(&self, predicted: &(T0, T1), expected: &(E0, E1)) -> f64 {
+        self.0.loss(&predicted.0, &expected.0) + self.1.loss(&predicted.1, &expected.1)
+    }
+
+    fn derive(&self, predicted: &(T0, T1), expected: &(E0, E1)) -> (U0, U1) {
+        (self.0.derive(&predicted.0, &expected.0), self.1.derive(&predicted.1, &expected.1))
+    }
+}
+
+impl<L0: IntoScaled, L1: IntoScaled> IntoScaled for (L0, L1) {}
+
+/// Applies every loss of the array to the matching output and sums their values
+impl<T, U, E, L: Loss<T, U, E>, const M: usize> Loss<[T; M], [U; M], [E; M]> for [L; M] {
+    fn loss(&self, predicted: &[T; M], expected: &[E; M]) -> f64 {
+        self.iter()
+            .zip(predicted.iter().zip(expected.iter()))
+            .map(|(l, (p, e))| l.loss(p, e))
+            .sum()
+    }
+
+    fn derive(&self, predicted: &[T; M], expected: &[E; M]) -> [U; M] {
+        self.each_ref()
+            .zip(predicted.each_ref().zip(expected.each_ref()))
+            .map(|(l, (p, e))| l.derive(p, e))
+    }
+}
+
+impl<L: IntoScaled, const M: usize> IntoScaled for [L; M] {}
+
+/// Multiplies a derivative by a scalar factor
+pub trait Scale {
+    fn scale(&mut self, factor: f64);
+}
+
+impl Scale for [Void; 0] {
+    fn scale(&mut self, _: f64) {}
+}
+
+impl<T: Number + From<f64>, B: BackendProvider, const N: usize> Scale for Tensor<T, B, N> {
+    fn scale(&mut self, factor: f64) {
+        let factor = T::from(factor);
+        self.iter_mut().for_each(|t| *t = *t * factor);
+    }
+}
+
+impl<A: Scale, const M: usize> Scale for [A; M] {
+    fn scale(&mut self, factor: f64) {
+        self.iter_mut().for_each(|a| a.scale(factor))
+    }
+}
+
+impl<A: Scale, C: Scale> Scale for (A, C) {
+    fn scale(&mut self, factor: f64) {
+        self.0.scale(factor);
+        self.1.scale(factor);
+    }
+}
+
+/// Loss that is a sum over independent entries of the prediction
+pub trait Pointwise<T> {
+    fn loss_at(&self, predicted: T, expected: T) -> f64;
+
+    fn derive_at(&self, predicted: T, expected: T) -> T;
+}
+
+/// Applies a [`Pointwise`] loss to every tensor of a [`Package`]
+///
+/// [`Package`]: crate::data::Package
+pub trait Zip<L> {
+    fn loss(loss: &L, a: &Self, b: &Self) -> f64;
+
+    fn zip(loss: &L, a: &Self, b: &Self) -> Self;
+}
+
+impl<L> Zip<L> for [Void; 0] {
+    fn loss(_: &L, []: &Self, []: &Self) -> f64 {
+        0.
+    }
+
+    fn zip(_: &L, []: &Self, []: &Self) -> Self {
+        []
+    }
+}
+
+impl<T: Copy, B: BackendProvider, L: Pointwise<T>, const N: usize> Zip<L> for Tensor<T, B, N> {
+    fn loss(loss: &L, a: &Self, b: &Self) -> f64 {
+        assert_eq!(a.shape(), b.shape());
+        a.iter()
+            .zip(b.iter())
+            .map(|(&a, &b)| loss.loss_at(a, b))
+            .sum()
+    }
+
+    fn zip(loss: &L, a: &Self, b: &Self) -> Self {
+        assert_eq!(a.shape(), b.shape());
+        a.shape().clone().into_tensor(|i| loss.derive_at(a[i], b[i]))
+    }
+}
+
+impl<L, A: Zip<L>, const M: usize> Zip<L> for [A; M] {
+    fn loss(loss: &L, a: &Self, b: &Self) -> f64 {
+        a.iter().zip(b.iter()).map(|(a, b)| Zip::loss(loss, a, b)).sum()
+    }
+
+    fn zip(loss: &L, a: &Self, b: &Self) -> Self {
+        a.each_ref().zip(b.each_ref()).map(|(a, b)| Zip::zip(loss, a, b))
+    }
+}
+
+impl<L, A: Zip<L>, C: Zip<L>> Zip<L> for (A, C) {
+    fn loss(loss: &L, a: &Self, b: &Self) -> f64 {
+        Zip::loss(loss, &a.0, &b.0) + Zip::loss(loss, &a.1, &b.1)
+    }
+
+    fn zip(loss: &L, a: &Self, b: &Self) -> Self {
+        (Zip::zip(loss, &a.0, &b.0), Zip::zip(loss, &a.1, &b.1))
+    }
+}
+
+/// Loss computed independently for every fiber of the last axis of the prediction
+pub trait Rowwise<T> {
+    fn loss_row(&self, predicted: &[T], expected: &[T]) -> f64;
+
+    fn derive_row(&self, predicted: &[T], expected: &[T], derivative: &mut [T]);
+}
+
+/// Applies a [`Rowwise`] loss to every tensor of a [`Package`]
+///
+/// [`Package`]: crate::data::Package
+pub trait Rows<L> {
+    fn loss(loss: &L, a: &Self, b: &Self) -> f64;
+
+    fn rows(loss: &L, a: &Self, b: &Self) -> Self;
+}
+
+impl<L> Rows<L> for [Void; 0] {
+    fn loss(_: &L, []: &Self, []: &Self) -> f64 {
+        0.
+    }
+
+    fn rows(_: &L, []: &Self, []: &Self) -> Self {
+        []
+    }
+}
+
+impl<T: Number, B: BackendProvider, L: Rowwise<T>, const N: usize> Rows<L> for Tensor<T, B, N> {
+    fn loss(loss: &L, a: &Self, b: &Self) -> f64 {
+        assert_eq!(a.shape(), b.shape());
+        fibers(a.shape(), Some(N - 1))
+            .map(|f| {
+                let predicted = f.clone().map(|i| a[i]).collect::<Vec<_>>();
+                let expected = f.map(|i| b[i]).collect::<Vec<_>>();
+                loss.loss_row(&predicted, &expected)
+            })
+            .sum()
+    }
+
+    fn rows(loss: &L, a: &Self, b: &Self) -> Self {
+        assert_eq!(a.shape(), b.shape());
+        let mut derivative = Tensor::new(T::zero(), a.shape().clone());
+        fibers(a.shape(), Some(N - 1)).for_each(|f| {
+            let predicted = f.clone().map(|i| a[i]).collect::<Vec<_>>();
+            let expected = f.clone().map(|i| b[i]).collect::<Vec<_>>();
+            let mut row = vec![T::zero(); predicted.len()];
+            loss.derive_row(&predicted, &expected, &mut row);
+            f.zip(row).for_each(|(i, d)| derivative[i] = d);
+        });
+        derivative
+    }
+}
+
+impl<L, A: Rows<L>, const M: usize> Rows<L> for [A; M] {
+    fn loss(loss: &L, a: &Self, b: &Self) -> f64 {
+        a.iter().zip(b.iter()).map(|(a, b)| Rows::loss(loss, a, b)).sum()
+    }
+
+    fn rows(loss: &L, a: &Self, b: &Self) -> Self {
+        a.each_ref().zip(b.each_ref()).map(|(a, b)| Rows::rows(loss, a, b))
+    }
+}
+
+impl<L, A: Rows<L>, C: Rows<L>> Rows<L> for (A, C) {
+    fn loss(loss: &L, a: &Self, b: &Self) -> f64 {
+        Rows::loss(loss, &a.0, &b.0) + Rows::loss(loss, &a.1, &b.1)
+    }
+
+    fn rows(loss: &L, a: &Self, b: &Self) -> Self {
+        (Rows::rows(loss, &a.0, &b.0), Rows::rows(loss, &a.1, &b.1))
+    }
+}
+
+/// Smallest probability used when taking logarithms of predictions
+pub(crate) const EPSILON: f64 = 1e-7;
+
+/// Clamps a probability to `[EPSILON, 1 - EPSILON]`
+pub(crate) fn clip<T: Float + From<f64>>(p: T) -> T {
+    let low = T::from(EPSILON);
+    let high = T::one() - low;
+    if p < low {
+        low
+    } else if p > high {
+        high
+    } else {
+        p
+    }
+}
+
+/// Numerically stable `ln(sum(exp(z)))`
+pub(crate) fn log_sum_exp<T: Float, I: Iterator<Item=T> + Clone>(z: I) -> T {
+    let max = z.clone()
+        .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .unwrap_or(T::zero());
+    max + z.map(|z| (z - max).exp()).sum::<T>().ln()
+}
+
+/// Only the entries of the leading axis unmasked in both the prediction and the expected value reach the inner loss,
+/// masked entries get a zero derivative
+impl<
+    I: ApplyMask<B>,
+    E: ApplyMask<B>,
+    U: ApplyMask<B>,
+    B: BackendProvider,
+    L: Loss<I, U, E>,
+> Loss<Masked<I, B>, U, Masked<E, B>> for L {
+    fn loss(&self, predicted: &Masked<I, B>, expected: &Masked<E, B>) -> f64 {
+        let mask = both(&predicted.mask, &expected.mask);
+        self.loss(&predicted.value.select(&mask), &expected.value.select(&mask))
+    }
+
+    fn derive(&self, predicted: &Masked<I, B>, expected: &Masked<E, B>) -> U {
+        let mask = both(&predicted.mask, &expected.mask);
+        U::scatter(self.derive(&predicted.value.select(&mask), &expected.value.select(&mask)), &mask)
+    }
+}
+
+fn both<B: BackendProvider>(a: &Tensor<bool, B, 1>, b: &Tensor<bool, B, 1>) -> Tensor<bool, B, 1> {
+    assert_eq!(a.shape(), b.shape());
+    a.shape().clone().into_tensor(|i| a[i] && b[i])
+}
+
+/// Masks out entries of the leading axis
+trait ApplyMask<B: BackendProvider> {
+    /// Keeps only the entries that are not masked out
+    fn select(&self, mask: &Tensor<bool, B, 1>) -> Self;
+
+    /// Inverse of [`ApplyMask::select`], masked out entries are zero
+    fn scatter(selected: Self, mask: &Tensor<bool, B, 1>) -> Self;
+}
+
+impl<B: BackendProvider> ApplyMask<B> for [Void; 0] {
+    fn select(&self, _: &Tensor<bool, B, 1>) -> Self {
+        []
+    }
+
+    fn scatter([]: Self, _: &Tensor<bool, B, 1>) -> Self {
+        []
+    }
+}
+
+impl<T: Number, B: BackendProvider, const N: usize> ApplyMask<B> for Tensor<T, B, N> {
+    fn select(&self, mask: &Tensor<bool, B, 1>) -> Self {
+        assert_eq!(self.shape()[0], mask.shape()[0]);
+        let step = self.shape().capacity() / mask.shape()[0];
+        let rows = (0..mask.shape()[0]).filter(|&r| mask[r]).collect::<Vec<_>>();
+        let shape = Shape::new(array::from_fn(|i| if i == 0 { rows.len() } else { self.shape()[i] }));
+        shape.into_tensor(|i| self[rows[i / step] * step + i % step])
+    }
+
+    fn scatter(selected: Self, mask: &Tensor<bool, B, 1>) -> Self {
+        let rows = (0..mask.shape()[0]).filter(|&r| mask[r]).collect::<Vec<_>>();
+        assert_eq!(selected.shape()[0], rows.len());
+        let step = selected.shape().capacity() / rows.len().max(1);
+        let shape = Shape::new(array::from_fn(|i| if i == 0 { mask.shape()[0] } else { selected.shape()[i] }));
+        let mut full = Tensor::new(T::zero(), shape);
+        rows.into_iter()
+            .enumerate()
+            .for_each(|(k, r)| (0..step).for_each(|j| full[r * step + j] = selected[k * step + j]));
+        full
+    }
+}
+
+impl<B: BackendProvider, A: ApplyMask<B>, const M: usize> ApplyMask<B> for [A; M] {
+    fn select(&self, mask: &Tensor<bool, B, 1>) -> Self {
+        self.each_ref().map(|a| a.select(mask))
+    }
+
+    fn scatter(selected: Self, mask: &Tensor<bool, B, 1>) -> Self {
+        selected.map(|a| A::scatter(a, mask))
+    }
+}
+
+impl<B: BackendProvider, A: ApplyMask<B>, C: ApplyMask<B>> ApplyMask<B> for (A, C) {
+    fn select(&self, mask: &Tensor<bool, B, 1>) -> Self {
+        (self.0.select(mask), self.1.select(mask))
+    }
+
+    fn scatter((a, c): Self, mask: &Tensor<bool, B, 1>) -> Self {
+        (A::scatter(a, mask), C::scatter(c, mask))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tensor::{Shape, Tensor, VecProvider};
+
+    use crate::{
+        data::Masked,
+        losses::{categorical_crossentropy::CategoricalCrossentropy, Loss},
+        testing::{assert_close, assert_gradient, numeric_gradient, tensor, values},
+    };
+
+    fn mask(mask: [bool; 3]) -> Tensor<bool, VecProvider, 1> {
+        Shape::new([3]).into_tensor(|i| mask[i])
+    }
+
+    #[test]
+    fn masked_loss_and_derivative_ignore_masked_rows() {
+        let loss = CategoricalCrossentropy::new(true, 0.);
+        let predicted = tensor([3, 2], &values(6, 1));
+        let expected = tensor([3, 2], &[1., 0., 0., 1., 0., 1.]);
+        let masked = |p: &Tensor<f64, VecProvider, 2>| Loss::<_, Tensor<f64, VecProvider, 2>, _>::loss(
+            &loss,
+            &Masked { value: p.clone(), mask: mask([true, true, false]) },
+            &Masked { value: expected.clone(), mask: mask([true, false, true]) },
+        );
+        let unmasked = loss.loss(&tensor([1, 2], &[predicted[0], predicted[1]]), &tensor([1, 2], &[1., 0.]));
+        assert_close(masked(&predicted), unmasked);
 
-    fn derive(&self, predicted: &T, expected: &T) -> U;
+        let numeric = numeric_gradient(&predicted, masked);
+        let derivative: Tensor<f64, VecProvider, 2> = loss.derive(
+            &Masked { value: predicted.clone(), mask: mask([true, true, false]) },
+            &Masked { value: expected.clone(), mask: mask([true, false, true]) },
+        );
+        assert_gradient(&derivative, &numeric);
+        assert!((2..6).all(|i| derivative[i] == 0.));
+    }
 }
@@ -0,0 +1,30 @@
+use num_traits::Float;
+
+use crate::losses::{Pointwise, EPSILON};
+
+/// Negative log-likelihood of `expected` under a Poisson distribution with rate `predicted`, up to a constant
+pub struct Poisson {
+    _private: (),
+}
+
+impl Poisson {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+
+    fn rate<T: Float + From<f64>>(predicted: T) -> T {
+        predicted + T::from(EPSILON)
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>> Pointwise<T> for Poisson {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        (predicted - expected * Self::rate(predicted).ln()).into()
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        T::one() - expected / Self::rate(predicted)
+    }
+}
+
+impl_pointwise!(Poisson);
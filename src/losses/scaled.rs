@@ -0,0 +1,37 @@
+use crate::{
+    data::Unmasked,
+    losses::{Loss, Scale},
+};
+
+/// Multiplies the value and the derivative of `loss` by a constant weight
+pub struct Scaled<L> {
+    loss: L,
+    weight: f64,
+}
+
+impl<L> Scaled<L> {
+    pub const fn new(loss: L, weight: f64) -> Self {
+        Self { loss, weight }
+    }
+}
+
+impl<T: Unmasked, U: Scale, E, L: Loss<T, U, E>> Loss<T, U, E> for Scaled<L> {
+    fn loss(&self, predicted: &T, expected: &E) -> f64 {
+        self.weight * self.loss.loss(predicted, expected)
+    }
+
+    fn derive(&self, predicted: &T, expected: &E) -> U {
+        let mut derivative = self.loss.derive(predicted, expected);
+        derivative.scale(self.weight);
+        derivative
+    }
+}
+
+/// Implemented by every loss, wraps it in [`Scaled`]
+pub trait IntoScaled: Sized {
+    fn scaled(self, weight: f64) -> Scaled<Self> {
+        Scaled::new(self, weight)
+    }
+}
+
+impl<L: IntoScaled> IntoScaled for Scaled<L> {}
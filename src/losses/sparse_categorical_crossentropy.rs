@@ -0,0 +1,73 @@
+use num_traits::Float;
+use tensor::{BackendProvider, Tensor};
+
+use crate::{
+    layers::fibers,
+    losses::{clip, log_sum_exp, scaled::IntoScaled, Loss},
+};
+
+/// [`CategoricalCrossentropy`] whose target is the index of the correct class for every fiber of the last axis
+///
+/// [`CategoricalCrossentropy`]: crate::losses::categorical_crossentropy::CategoricalCrossentropy
+pub struct SparseCategoricalCrossentropy {
+    from_logits: bool,
+    label_smoothing: f64,
+}
+
+impl SparseCategoricalCrossentropy {
+    pub const fn new(from_logits: bool, label_smoothing: f64) -> Self {
+        Self {
+            from_logits,
+            label_smoothing,
+        }
+    }
+
+    fn smooth<T: Float + From<f64>>(&self, class: usize, label: usize, classes: usize) -> T {
+        let label_smoothing = T::from(self.label_smoothing);
+        let expected = if class == label { T::one() } else { T::zero() };
+        expected * (T::one() - label_smoothing) + label_smoothing / T::from(classes as f64)
+    }
+}
+
+impl<
+    T: Float + From<f64> + Into<f64>,
+    B: BackendProvider,
+    const N: usize,
+> Loss<Tensor<T, B, N>, Tensor<T, B, N>, Tensor<usize, B, { N - 1 }>> for SparseCategoricalCrossentropy where [(); N - 1]: {
+    fn loss(&self, predicted: &Tensor<T, B, N>, expected: &Tensor<usize, B, { N - 1 }>) -> f64 {
+        let classes = predicted.shape()[N - 1];
+        assert_eq!(predicted.shape().capacity(), expected.shape().capacity() * classes);
+        fibers(predicted.shape(), Some(N - 1))
+            .enumerate()
+            .map(|(r, f)| {
+                let label = expected[r];
+                assert!(label < classes);
+                let log_sum = if self.from_logits { log_sum_exp(f.clone().map(|i| predicted[i])) } else { T::zero() };
+                f.enumerate().map(|(c, i)| {
+                    let log_p = if self.from_logits { predicted[i] - log_sum } else { clip(predicted[i]).ln() };
+                    let loss: f64 = (-self.smooth::<T>(c, label, classes) * log_p).into();
+                    loss
+                }).sum::<f64>()
+            })
+            .sum()
+    }
+
+    fn derive(&self, predicted: &Tensor<T, B, N>, expected: &Tensor<usize, B, { N - 1 }>) -> Tensor<T, B, N> {
+        let classes = predicted.shape()[N - 1];
+        assert_eq!(predicted.shape().capacity(), expected.shape().capacity() * classes);
+        let mut derivative = Tensor::new(T::zero(), predicted.shape().clone());
+        fibers(predicted.shape(), Some(N - 1)).enumerate().for_each(|(r, f)| {
+            let label = expected[r];
+            assert!(label < classes);
+            if self.from_logits {
+                let log_sum = log_sum_exp(f.clone().map(|i| predicted[i]));
+                f.enumerate().for_each(|(c, i)| derivative[i] = (predicted[i] - log_sum).exp() - self.smooth(c, label, classes));
+            } else {
+                f.enumerate().for_each(|(c, i)| derivative[i] = -self.smooth::<T>(c, label, classes) / clip(predicted[i]));
+            }
+        });
+        derivative
+    }
+}
+
+impl IntoScaled for SparseCategoricalCrossentropy {}
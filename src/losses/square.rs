@@ -1,8 +1,6 @@
 use std::ops::{Mul, Sub};
-use tensor::{BackendProvider, Tensor};
-use void::Void;
 
-use crate::losses::Loss;
+use crate::losses::Pointwise;
 
 pub struct Square {
     _private: (),
@@ -14,44 +12,15 @@ impl Square {
     }
 }
 
-impl<I: Zip> Loss<I, I> for Square {
-    // fn loss(&self, predicted: &[Tensor<T>; N], expected: &[Tensor<T>; N]) -> T {
-    //     predicted
-    //         .iter()
-    //         .zip(expected.iter())
-    //         .map(|(a, b)| a.iter().copied().zip(b.iter().copied()).map(|(a, b)| (a - b) * (a - b)).sum())
-    //         .sum()
-    // }
-
-    fn derive(&self, predicted: &I, expected: &I) -> I {
-        I::zip(predicted, expected)
-    }
-}
-
-trait Zip {
-    fn zip(a: &Self, b: &Self) -> Self;
-}
-
-impl Zip for [Void; 0] {
-    fn zip([]: &Self, []: &Self) -> Self {
-        []
+impl<T: From<i32> + Into<f64> + Copy + Mul<Output=T> + Sub<Output=T>> Pointwise<T> for Square {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        let d = predicted - expected;
+        (d * d).into()
     }
-}
-
-impl<T: From<i32> + Clone + Mul<Output=T>, B: BackendProvider, const N: usize> Zip for Tensor<T, B, N> where for<'s> &'s T: Sub<&'s T, Output=T> {
-    fn zip(a: &Self, b: &Self) -> Self {
-        (a - b) * T::from(2)
-    }
-}
 
-impl<T: From<i32> + Clone + Mul<Output=T>, B: BackendProvider, const N: usize, const L: usize> Zip for [Tensor<T, B, N>; L] where for<'s> &'s T: Sub<&'s T, Output=T> {
-    fn zip(a: &Self, b: &Self) -> Self {
-        a.each_ref().zip(b.each_ref()).map(|(a, b)| Zip::zip(a, b))
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        (predicted - expected) * T::from(2)
     }
 }
 
-impl<T: Zip, U: Zip> Zip for (T, U) {
-    fn zip(a: &Self, b: &Self) -> Self {
-        (Zip::zip(&a.0, &b.0), Zip::zip(&a.1, &b.1))
-    }
-}
+impl_pointwise!(Square);
@@ -0,0 +1,32 @@
+use num_traits::Number;
+
+use crate::losses::Pointwise;
+
+/// `max(0, 1 - expected * predicted)^2` for targets in `{-1, 1}`
+pub struct SquaredHinge {
+    _private: (),
+}
+
+impl SquaredHinge {
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+impl<T: Number + Into<f64>> Pointwise<T> for SquaredHinge {
+    fn loss_at(&self, predicted: T, expected: T) -> f64 {
+        let margin = T::one() - expected * predicted;
+        if margin > T::zero() { (margin * margin).into() } else { 0. }
+    }
+
+    fn derive_at(&self, predicted: T, expected: T) -> T {
+        let margin = T::one() - expected * predicted;
+        if margin > T::zero() {
+            T::zero() - (margin + margin) * expected
+        } else {
+            T::zero()
+        }
+    }
+}
+
+impl_pointwise!(SquaredHinge);
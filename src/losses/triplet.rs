@@ -0,0 +1,99 @@
+use num_traits::Float;
+use tensor::{BackendProvider, Tensor};
+use void::Void;
+
+use crate::losses::{scaled::IntoScaled, Loss};
+
+/// `max(0, |anchor - positive|^2 - |anchor - negative|^2 + margin)` for every row of
+/// anchor, positive and negative embeddings of shape `[batch, dimension]`
+pub struct TripletLoss {
+    margin: f64,
+}
+
+impl TripletLoss {
+    pub const fn new(margin: f64) -> Self {
+        Self { margin }
+    }
+
+    fn squared_distance<T: Float, B: BackendProvider>(a: &Tensor<T, B, 2>, b: &Tensor<T, B, 2>, i: usize) -> T {
+        (0..a.shape()[1]).map(|k| (a[[i, k]] - b[[i, k]]) * (a[[i, k]] - b[[i, k]])).sum()
+    }
+
+    fn violation<T: Float + From<f64>, B: BackendProvider>(
+        &self,
+        anchor: &Tensor<T, B, 2>,
+        positive: &Tensor<T, B, 2>,
+        negative: &Tensor<T, B, 2>,
+        i: usize,
+    ) -> T {
+        Self::squared_distance(anchor, positive, i) - Self::squared_distance(anchor, negative, i) + T::from(self.margin)
+    }
+}
+
+impl<T: Float + From<f64> + Into<f64>, B: BackendProvider> Loss<
+    [Tensor<T, B, 2>; 3],
+    [Tensor<T, B, 2>; 3],
+    [Void; 0],
+> for TripletLoss {
+    fn loss(&self, [anchor, positive, negative]: &[Tensor<T, B, 2>; 3], []: &[Void; 0]) -> f64 {
+        assert_eq!(anchor.shape(), positive.shape());
+        assert_eq!(anchor.shape(), negative.shape());
+        (0..anchor.shape()[0])
+            .map(|i| self.violation(anchor, positive, negative, i))
+            .filter(|&v| v > T::zero())
+            .map(Into::<f64>::into)
+            .sum()
+    }
+
+    fn derive(&self, [anchor, positive, negative]: &[Tensor<T, B, 2>; 3], []: &[Void; 0]) -> [Tensor<T, B, 2>; 3] {
+        assert_eq!(anchor.shape(), positive.shape());
+        assert_eq!(anchor.shape(), negative.shape());
+        let two = T::from(2.);
+        let mut derivatives = [(); 3].map(|_| Tensor::new(T::zero(), anchor.shape().clone()));
+        for i in 0..anchor.shape()[0] {
+            if self.violation(anchor, positive, negative, i) <= T::zero() {
+                continue;
+            }
+            for k in 0..anchor.shape()[1] {
+                let (a, p, n) = (anchor[[i, k]], positive[[i, k]], negative[[i, k]]);
+                derivatives[0][[i, k]] = two * (n - p);
+                derivatives[1][[i, k]] = two * (p - a);
+                derivatives[2][[i, k]] = two * (a - n);
+            }
+        }
+        derivatives
+    }
+}
+
+impl IntoScaled for TripletLoss {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        losses::Loss,
+        testing::{assert_close, assert_gradient, numeric_gradient, tensor, values},
+    };
+    use super::TripletLoss;
+
+    #[test]
+    fn only_violating_rows_count() {
+        let anchor = tensor([2, 1], &[0., 0.]);
+        let positive = tensor([2, 1], &[1., 0.]);
+        let negative = tensor([2, 1], &[2., 0.5]);
+        // row 0: 1 - 4 + 1 < 0, row 1: 0 - 0.25 + 1 = 0.75
+        assert_close(TripletLoss::new(1.).loss(&[anchor, positive, negative], &[]), 0.75);
+    }
+
+    #[test]
+    fn gradient() {
+        let loss = TripletLoss::new(1.);
+        let inputs = [1, 2, 3].map(|seed| tensor([4, 3], &values(12, seed)));
+        let numeric = [0, 1, 2].map(|part| numeric_gradient(&inputs[part], |x| {
+            let mut inputs = inputs.clone();
+            inputs[part] = x.clone();
+            loss.loss(&inputs, &[])
+        }));
+        let derivatives = loss.derive(&inputs, &[]);
+        (0..3).for_each(|part| assert_gradient(&derivatives[part], &numeric[part]));
+    }
+}
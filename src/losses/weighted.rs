@@ -0,0 +1,98 @@
+use tensor::{BackendProvider, Tensor};
+
+use crate::{
+    data::Unmasked,
+    losses::{scaled::IntoScaled, Loss, Scale},
+};
+
+/// Weight of an example computed from its expected value
+pub trait Weighting<E> {
+    fn weight(&self, expected: &E) -> f64;
+}
+
+impl<E, F: Fn(&E) -> f64> Weighting<E> for F {
+    fn weight(&self, expected: &E) -> f64 {
+        self(expected)
+    }
+}
+
+/// Weight for every class of sparse integer labels or of one-hot (or soft) targets over the last axis
+///
+/// Examples with several labels get the mean weight of their labels.
+pub struct ClassWeights {
+    weights: Vec<f64>,
+}
+
+impl ClassWeights {
+    pub fn new(weights: Vec<f64>) -> Self {
+        Self { weights }
+    }
+
+    /// Weights every class by `total / (classes * count)`, so that every class contributes equally
+    pub fn balanced(counts: &[usize]) -> Self {
+        let total = counts.iter().sum::<usize>() as f64;
+        let classes = counts.len() as f64;
+        Self {
+            weights: counts.iter().map(|&c| if c == 0 { 0. } else { total / (classes * c as f64) }).collect(),
+        }
+    }
+}
+
+impl<B: BackendProvider, const N: usize> Weighting<Tensor<usize, B, N>> for ClassWeights {
+    fn weight(&self, expected: &Tensor<usize, B, N>) -> f64 {
+        let count = expected.shape().capacity();
+        expected.iter()
+            .map(|&c| {
+                assert!(c < self.weights.len(), "label {c} is out of range for {} class weights", self.weights.len());
+                self.weights[c]
+            })
+            .sum::<f64>() / count.max(1) as f64
+    }
+}
+
+macro_rules! impl_one_hot_weighting {
+    ($($t:ty),*) => {
+        $(
+            /// Every target row is weighted by the class weights averaged with its probabilities
+            impl<B: BackendProvider, const N: usize> Weighting<Tensor<$t, B, N>> for ClassWeights {
+                fn weight(&self, expected: &Tensor<$t, B, N>) -> f64 {
+                    let classes = expected.shape()[N - 1];
+                    assert_eq!(classes, self.weights.len(), "targets have {classes} classes but {} class weights are configured", self.weights.len());
+                    let rows = expected.shape().capacity() / classes.max(1);
+                    expected.iter()
+                        .enumerate()
+                        .map(|(i, &e)| e as f64 * self.weights[i % classes])
+                        .sum::<f64>() / rows.max(1) as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_one_hot_weighting!(f32, f64);
+
+/// Scales the value and the derivative of `loss` by the weight of every example
+pub struct Weighted<L, W> {
+    loss: L,
+    weighting: W,
+}
+
+impl<L, W> Weighted<L, W> {
+    pub const fn new(loss: L, weighting: W) -> Self {
+        Self { loss, weighting }
+    }
+}
+
+impl<T: Unmasked, U: Scale, E, L: Loss<T, U, E>, W: Weighting<E>> Loss<T, U, E> for Weighted<L, W> {
+    fn loss(&self, predicted: &T, expected: &E) -> f64 {
+        self.weighting.weight(expected) * self.loss.loss(predicted, expected)
+    }
+
+    fn derive(&self, predicted: &T, expected: &E) -> U {
+        let mut derivative = self.loss.derive(predicted, expected);
+        derivative.scale(self.weighting.weight(expected));
+        derivative
+    }
+}
+
+impl<L: IntoScaled, W> IntoScaled for Weighted<L, W> {}
@@ -0,0 +1,220 @@
+use std::{
+    cmp::Ordering,
+    marker::PhantomData,
+};
+
+use num_traits::Number;
+use tensor::{BackendProvider, Tensor};
+
+use crate::{
+    layers::fibers,
+    metrics::{classes, Metric},
+};
+
+/// Running count of correct predictions
+#[derive(Default)]
+struct Count {
+    correct: usize,
+    total: usize,
+}
+
+impl Count {
+    fn add(&mut self, correct: bool) {
+        self.correct += correct as usize;
+        self.total += 1;
+    }
+
+    fn result(&self) -> f64 {
+        if self.total == 0 { 0. } else { self.correct as f64 / self.total as f64 }
+    }
+}
+
+/// Fraction of fibers of the last axis whose most probable class matches the one-hot target
+pub struct Accuracy<T, B, const N: usize> {
+    count: Count,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<T, B, const N: usize> Accuracy<T, B, N> {
+    pub fn new() -> Self {
+        Self {
+            count: Count::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Number + From<f64>, B: BackendProvider, const N: usize> Metric for Accuracy<T, B, N> {
+    type Input = (Tensor<T, B, N>, Tensor<T, B, N>);
+    type Result = f64;
+
+    fn update(&mut self, (predicted, expected): &Self::Input) {
+        assert_eq!(predicted.shape(), expected.shape());
+        classes(predicted)
+            .into_iter()
+            .zip(classes(expected))
+            .for_each(|(p, e)| self.count.add(p == e));
+    }
+
+    fn reset(&mut self) {
+        self.count = Count::default();
+    }
+
+    fn result(&self) -> Self::Result {
+        self.count.result()
+    }
+}
+
+/// Fraction of entries whose prediction falls on the same side of `threshold` as the target
+pub struct BinaryAccuracy<T, B, const N: usize> {
+    threshold: T,
+    count: Count,
+    _marker: PhantomData<B>,
+}
+
+impl<T, B, const N: usize> BinaryAccuracy<T, B, N> {
+    pub fn new(threshold: T) -> Self {
+        Self {
+            threshold,
+            count: Count::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Number + From<f64>, B: BackendProvider, const N: usize> Metric for BinaryAccuracy<T, B, N> {
+    type Input = (Tensor<T, B, N>, Tensor<T, B, N>);
+    type Result = f64;
+
+    fn update(&mut self, (predicted, expected): &Self::Input) {
+        assert_eq!(predicted.shape(), expected.shape());
+        predicted.iter()
+            .zip(expected.iter())
+            .for_each(|(&p, &e)| self.count.add((p > self.threshold) == (e > T::from(0.5))));
+    }
+
+    fn reset(&mut self) {
+        self.count = Count::default();
+    }
+
+    fn result(&self) -> Self::Result {
+        self.count.result()
+    }
+}
+
+/// [`Accuracy`] with the index of the correct class of every fiber of the last axis as the target
+pub struct SparseCategoricalAccuracy<T, B, const N: usize> {
+    count: Count,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<T, B, const N: usize> SparseCategoricalAccuracy<T, B, N> {
+    pub fn new() -> Self {
+        Self {
+            count: Count::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Number + From<f64>, B: BackendProvider, const N: usize> Metric for SparseCategoricalAccuracy<T, B, N> where [(); N - 1]: {
+    type Input = (Tensor<T, B, N>, Tensor<usize, B, { N - 1 }>);
+    type Result = f64;
+
+    fn update(&mut self, (predicted, expected): &Self::Input) {
+        let predicted = classes(predicted);
+        assert_eq!(predicted.len(), expected.shape().capacity());
+        predicted.into_iter()
+            .zip(expected.iter())
+            .for_each(|(p, &e)| self.count.add(p == e));
+    }
+
+    fn reset(&mut self) {
+        self.count = Count::default();
+    }
+
+    fn result(&self) -> Self::Result {
+        self.count.result()
+    }
+}
+
+/// Fraction of fibers of the last axis whose one-hot target is among the `k` most probable classes
+///
+/// Fibers of length one hold the probability of class `1` of a binary classifier,
+/// whose prediction is correct when it falls on the same side of `0.5` as the target, whatever `k` is.
+pub struct TopKAccuracy<T, B, const N: usize> {
+    k: usize,
+    count: Count,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<T, B, const N: usize> TopKAccuracy<T, B, N> {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            count: Count::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Number + From<f64>, B: BackendProvider, const N: usize> Metric for TopKAccuracy<T, B, N> {
+    type Input = (Tensor<T, B, N>, Tensor<T, B, N>);
+    type Result = f64;
+
+    fn update(&mut self, (predicted, expected): &Self::Input) {
+        assert_eq!(predicted.shape(), expected.shape());
+        if predicted.shape()[N - 1] == 1 {
+            classes(predicted)
+                .into_iter()
+                .zip(classes(expected))
+                .for_each(|(p, e)| self.count.add(p == e));
+            return;
+        }
+        fibers(predicted.shape(), Some(N - 1))
+            .zip(classes(expected))
+            .for_each(|(f, e)| {
+                let f = f.collect::<Vec<_>>();
+                let mut order = (0..f.len()).collect::<Vec<_>>();
+                order.sort_by(|&a, &b| predicted[f[b]].partial_cmp(&predicted[f[a]]).unwrap_or(Ordering::Equal));
+                self.count.add(order.iter().take(self.k).any(|&c| c == e));
+            });
+    }
+
+    fn reset(&mut self) {
+        self.count = Count::default();
+    }
+
+    fn result(&self) -> Self::Result {
+        self.count.result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tensor::VecProvider;
+
+    use crate::{
+        metrics::Metric,
+        testing::{assert_close, tensor},
+    };
+    use super::TopKAccuracy;
+
+    #[test]
+    fn counts_targets_among_the_k_most_probable_classes() {
+        let mut metric = TopKAccuracy::<f64, VecProvider, 2>::new(2);
+        let predicted = tensor([3, 3], &[0.5, 0.3, 0.2, 0.1, 0.3, 0.6, 0.2, 0.7, 0.1]);
+        let expected = tensor([3, 3], &[0., 1., 0., 1., 0., 0., 0., 0., 1.]);
+        metric.update(&(predicted, expected));
+        assert_close(metric.result(), 1. / 3.);
+    }
+
+    #[test]
+    fn thresholds_a_single_binary_probability() {
+        let mut metric = TopKAccuracy::<f64, VecProvider, 2>::new(1);
+        let predicted = tensor([4, 1], &[0.9, 0.2, 0.6, 0.4]);
+        let expected = tensor([4, 1], &[1., 0., 0., 1.]);
+        metric.update(&(predicted, expected));
+        assert_close(metric.result(), 0.5);
+    }
+}
@@ -0,0 +1,336 @@
+use std::marker::PhantomData;
+
+use num_traits::Number;
+use tensor::{BackendProvider, Tensor};
+
+use crate::metrics::Metric;
+
+/// Counts of positive and negative entries bucketed by their predicted probability
+///
+/// Bucket `j` holds the predictions in `[j, j + 1) / (thresholds - 1)`,
+/// so the counts at threshold `j / (thresholds - 1)` are suffix sums over the buckets.
+struct Buckets {
+    positives: Vec<usize>,
+    negatives: Vec<usize>,
+}
+
+/// True positive, false positive, true negative and false negative counts at one threshold
+#[derive(Copy, Clone)]
+struct Counts {
+    tp: usize,
+    fp: usize,
+    tn: usize,
+    fn_: usize,
+}
+
+impl Counts {
+    fn precision(&self) -> f64 {
+        if self.tp + self.fp == 0 { 1. } else { self.tp as f64 / (self.tp + self.fp) as f64 }
+    }
+
+    fn recall(&self) -> f64 {
+        if self.tp + self.fn_ == 0 { 0. } else { self.tp as f64 / (self.tp + self.fn_) as f64 }
+    }
+
+    fn false_positive_rate(&self) -> f64 {
+        if self.fp + self.tn == 0 { 0. } else { self.fp as f64 / (self.fp + self.tn) as f64 }
+    }
+}
+
+impl Buckets {
+    fn new(thresholds: usize) -> Self {
+        assert!(thresholds > 1);
+        Self {
+            positives: vec![0; thresholds],
+            negatives: vec![0; thresholds],
+        }
+    }
+
+    fn bucket(&self, p: f64) -> usize {
+        let last = self.positives.len() - 1;
+        let b = (p * last as f64).floor();
+        if b < 0. { 0 } else if b > last as f64 { last } else { b as usize }
+    }
+
+    fn update<T: Number + Into<f64>, B: BackendProvider, const N: usize>(&mut self, predicted: &Tensor<T, B, N>, expected: &Tensor<T, B, N>) {
+        assert_eq!(predicted.shape(), expected.shape());
+        predicted.iter().zip(expected.iter()).for_each(|(&p, &e)| {
+            let b = self.bucket(p.into());
+            if e.into() > 0.5 {
+                self.positives[b] += 1
+            } else {
+                self.negatives[b] += 1
+            }
+        });
+    }
+
+    fn reset(&mut self) {
+        self.positives.iter_mut().chain(self.negatives.iter_mut()).for_each(|c| *c = 0);
+    }
+
+    /// Counts at every threshold, from the lowest to the highest
+    fn counts(&self) -> Vec<Counts> {
+        let positives = self.positives.iter().sum::<usize>();
+        let negatives = self.negatives.iter().sum::<usize>();
+        let (mut tp, mut fp) = (0, 0);
+        let mut counts = (0..self.positives.len())
+            .rev()
+            .map(|j| {
+                tp += self.positives[j];
+                fp += self.negatives[j];
+                Counts { tp, fp, tn: negatives - fp, fn_: positives - tp }
+            })
+            .collect::<Vec<_>>();
+        counts.reverse();
+        counts
+    }
+}
+
+/// Curve whose area [`AUC`] computes
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum Curve {
+    /// True positive rate against false positive rate
+    ROC,
+    /// Precision against recall
+    PR,
+}
+
+/// Area under the ROC or precision-recall curve of binary predictions,
+/// approximated with `thresholds` evenly spaced thresholds in `[0, 1]`
+pub struct AUC<T, B, const N: usize> {
+    curve: Curve,
+    buckets: Buckets,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<T, B, const N: usize> AUC<T, B, N> {
+    pub fn new(curve: Curve, thresholds: usize) -> Self {
+        Self {
+            curve,
+            buckets: Buckets::new(thresholds),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Number + Into<f64>, B: BackendProvider, const N: usize> Metric for AUC<T, B, N> {
+    type Input = (Tensor<T, B, N>, Tensor<T, B, N>);
+    type Result = f64;
+
+    fn update(&mut self, (predicted, expected): &Self::Input) {
+        self.buckets.update(predicted, expected)
+    }
+
+    fn reset(&mut self) {
+        self.buckets.reset()
+    }
+
+    fn result(&self) -> Self::Result {
+        // points ordered by decreasing threshold, starting at the point where nothing is predicted positive
+        let counts = self.buckets.counts();
+        let points = counts.iter().rev().map(|c| match self.curve {
+            Curve::ROC => (c.false_positive_rate(), c.recall()),
+            Curve::PR => (c.recall(), c.precision()),
+        });
+        let start = match self.curve {
+            Curve::ROC => (0., 0.),
+            Curve::PR => (0., 1.),
+        };
+        points
+            .fold((start, 0.), |((x0, y0), area), (x1, y1)| ((x1, y1), area + (x1 - x0) * (y0 + y1) / 2.))
+            .1
+    }
+}
+
+/// Highest precision at any threshold whose recall is at least `recall`
+pub struct PrecisionAtRecall<T, B, const N: usize> {
+    recall: f64,
+    buckets: Buckets,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<T, B, const N: usize> PrecisionAtRecall<T, B, N> {
+    pub fn new(recall: f64, thresholds: usize) -> Self {
+        Self {
+            recall,
+            buckets: Buckets::new(thresholds),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Number + Into<f64>, B: BackendProvider, const N: usize> Metric for PrecisionAtRecall<T, B, N> {
+    type Input = (Tensor<T, B, N>, Tensor<T, B, N>);
+    type Result = f64;
+
+    fn update(&mut self, (predicted, expected): &Self::Input) {
+        self.buckets.update(predicted, expected)
+    }
+
+    fn reset(&mut self) {
+        self.buckets.reset()
+    }
+
+    fn result(&self) -> Self::Result {
+        self.buckets
+            .counts()
+            .iter()
+            .filter(|c| c.recall() >= self.recall)
+            .map(Counts::precision)
+            .fold(0., f64::max)
+    }
+}
+
+/// Highest recall at any threshold whose precision is at least `precision`
+pub struct RecallAtPrecision<T, B, const N: usize> {
+    precision: f64,
+    buckets: Buckets,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<T, B, const N: usize> RecallAtPrecision<T, B, N> {
+    pub fn new(precision: f64, thresholds: usize) -> Self {
+        Self {
+            precision,
+            buckets: Buckets::new(thresholds),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Number + Into<f64>, B: BackendProvider, const N: usize> Metric for RecallAtPrecision<T, B, N> {
+    type Input = (Tensor<T, B, N>, Tensor<T, B, N>);
+    type Result = f64;
+
+    fn update(&mut self, (predicted, expected): &Self::Input) {
+        self.buckets.update(predicted, expected)
+    }
+
+    fn reset(&mut self) {
+        self.buckets.reset()
+    }
+
+    fn result(&self) -> Self::Result {
+        self.buckets
+            .counts()
+            .iter()
+            .filter(|c| c.tp + c.fp > 0 && c.precision() >= self.precision)
+            .map(Counts::recall)
+            .fold(0., f64::max)
+    }
+}
+
+/// Mean absolute difference between the predicted probability and the observed frequency of positives,
+/// over `bins` evenly spaced probability bins weighted by the number of predictions in them
+pub struct ExpectedCalibrationError<T, B, const N: usize> {
+    confidence: Vec<f64>,
+    positives: Vec<f64>,
+    counts: Vec<usize>,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<T, B, const N: usize> ExpectedCalibrationError<T, B, N> {
+    pub fn new(bins: usize) -> Self {
+        assert!(bins > 0);
+        Self {
+            confidence: vec![0.; bins],
+            positives: vec![0.; bins],
+            counts: vec![0; bins],
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Number + Into<f64>, B: BackendProvider, const N: usize> Metric for ExpectedCalibrationError<T, B, N> {
+    type Input = (Tensor<T, B, N>, Tensor<T, B, N>);
+    type Result = f64;
+
+    fn update(&mut self, (predicted, expected): &Self::Input) {
+        assert_eq!(predicted.shape(), expected.shape());
+        let bins = self.counts.len();
+        predicted.iter().zip(expected.iter()).for_each(|(&p, &e)| {
+            let p: f64 = p.into();
+            let b = ((p * bins as f64) as usize).min(bins - 1);
+            self.confidence[b] += p;
+            self.positives[b] += if e.into() > 0.5 { 1. } else { 0. };
+            self.counts[b] += 1;
+        });
+    }
+
+    fn reset(&mut self) {
+        self.confidence.iter_mut().chain(self.positives.iter_mut()).for_each(|c| *c = 0.);
+        self.counts.iter_mut().for_each(|c| *c = 0);
+    }
+
+    fn result(&self) -> Self::Result {
+        let total = self.counts.iter().sum::<usize>();
+        if total == 0 {
+            return 0.;
+        }
+        self.counts
+            .iter()
+            .zip(self.confidence.iter().zip(&self.positives))
+            .filter(|(&count, _)| count > 0)
+            .map(|(&count, (&confidence, &positives))| (confidence - positives).abs() / total as f64)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tensor::{Tensor, VecProvider};
+
+    use crate::{
+        metrics::Metric,
+        testing::{assert_close, tensor},
+    };
+    use super::{AUC, Curve, ExpectedCalibrationError, PrecisionAtRecall, RecallAtPrecision};
+
+    /// Predictions in buckets `1, 4, 3, 8` of 11 thresholds, where the last two are positives
+    fn input() -> (Tensor<f64, VecProvider, 1>, Tensor<f64, VecProvider, 1>) {
+        (tensor([4], &[0.1, 0.4, 0.35, 0.8]), tensor([4], &[0., 0., 1., 1.]))
+    }
+
+    fn result<M: Metric<Input=(Tensor<f64, VecProvider, 1>, Tensor<f64, VecProvider, 1>), Result=f64>>(mut metric: M) -> f64 {
+        metric.update(&input());
+        metric.result()
+    }
+
+    #[test]
+    fn roc_auc() {
+        // (0, 1/2) to (1/2, 1/2) and (1/2, 1) to (1, 1)
+        assert_close(result(AUC::new(Curve::ROC, 11)), 0.75);
+    }
+
+    #[test]
+    fn pr_auc() {
+        // (0, 1) to (1/2, 1) and (1/2, 1/2) to (1, 2/3)
+        assert_close(result(AUC::new(Curve::PR, 11)), 0.5 + 0.5 * (0.5 + 2. / 3.) / 2.);
+    }
+
+    #[test]
+    fn precision_at_recall() {
+        assert_close(result(PrecisionAtRecall::new(1., 11)), 2. / 3.);
+        assert_close(result(PrecisionAtRecall::new(0.5, 11)), 1.);
+    }
+
+    #[test]
+    fn recall_at_precision() {
+        assert_close(result(RecallAtPrecision::new(1., 11)), 0.5);
+        assert_close(result(RecallAtPrecision::new(0.6, 11)), 1.);
+    }
+
+    #[test]
+    fn expected_calibration_error() {
+        // bin 0: |0.85 - 1| over three predictions, bin 1: |0.8 - 1| over one
+        assert_close(result(ExpectedCalibrationError::new(2)), (0.15 + 0.2) / 4.);
+    }
+
+    #[test]
+    fn reset_clears_the_buckets() {
+        let mut metric = AUC::<f64, VecProvider, 1>::new(Curve::ROC, 11);
+        metric.update(&input());
+        metric.reset();
+        assert_eq!(metric.result(), 0.);
+    }
+}
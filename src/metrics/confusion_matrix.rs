@@ -0,0 +1,175 @@
+use std::marker::PhantomData;
+
+use num_traits::Number;
+use tensor::{BackendProvider, Tensor};
+
+use crate::metrics::{classes, Metric};
+
+/// Counts of every pair of expected (row) and predicted (column) class
+///
+/// Classes are taken from the fibers of the last axis as in [`Accuracy`];
+/// fibers of length one are thresholded at `0.5`, which gives two classes.
+///
+/// [`Accuracy`]: crate::metrics::accuracy::Accuracy
+pub struct ConfusionMatrix<T, B, const N: usize> {
+    classes: usize,
+    counts: Vec<Vec<usize>>,
+    _marker: PhantomData<(T, B)>,
+}
+
+impl<T, B, const N: usize> ConfusionMatrix<T, B, N> {
+    pub fn new(classes: usize) -> Self {
+        assert!(classes >= 2, "a confusion matrix needs at least two classes");
+        Self {
+            classes,
+            counts: vec![vec![0; classes]; classes],
+            _marker: PhantomData,
+        }
+    }
+
+    fn true_positives(&self, class: usize) -> usize {
+        self.counts[class][class]
+    }
+
+    fn false_positives(&self, class: usize) -> usize {
+        (0..self.classes).filter(|&e| e != class).map(|e| self.counts[e][class]).sum()
+    }
+
+    fn false_negatives(&self, class: usize) -> usize {
+        (0..self.classes).filter(|&p| p != class).map(|p| self.counts[class][p]).sum()
+    }
+}
+
+impl<T: Number + From<f64>, B: BackendProvider, const N: usize> Metric for ConfusionMatrix<T, B, N> {
+    type Input = (Tensor<T, B, N>, Tensor<T, B, N>);
+    type Result = Vec<Vec<usize>>;
+
+    fn update(&mut self, (predicted, expected): &Self::Input) {
+        assert_eq!(predicted.shape(), expected.shape());
+        let width = predicted.shape()[N - 1];
+        let classes_of_width = if width == 1 { 2 } else { width };
+        assert_eq!(
+            classes_of_width,
+            self.classes,
+            "fibers of length {width} give {classes_of_width} classes but the confusion matrix has {}",
+            self.classes,
+        );
+        classes(predicted)
+            .into_iter()
+            .zip(classes(expected))
+            .for_each(|(p, e)| self.counts[e][p] += 1);
+    }
+
+    fn reset(&mut self) {
+        self.counts.iter_mut().flatten().for_each(|c| *c = 0);
+    }
+
+    fn result(&self) -> Self::Result {
+        self.counts.clone()
+    }
+}
+
+/// How the per-class scores of [`Precision`], [`Recall`] and [`F1Score`] are combined
+#[derive(Copy, Clone)]
+pub enum Average {
+    /// Score of class `1` only
+    Binary,
+    /// Score of the summed counts of every class
+    Micro,
+    /// Unweighted mean of the scores of every class
+    Macro,
+}
+
+/// `numerator / (numerator + other)`, zero when both are zero
+fn ratio(numerator: usize, other: usize) -> f64 {
+    if numerator + other == 0 { 0. } else { numerator as f64 / (numerator + other) as f64 }
+}
+
+fn f1(precision: f64, recall: f64) -> f64 {
+    if precision + recall == 0. { 0. } else { 2. * precision * recall / (precision + recall) }
+}
+
+impl<T, B, const N: usize> ConfusionMatrix<T, B, N> {
+    fn precision_of(&self, class: usize) -> f64 {
+        ratio(self.true_positives(class), self.false_positives(class))
+    }
+
+    fn recall_of(&self, class: usize) -> f64 {
+        ratio(self.true_positives(class), self.false_negatives(class))
+    }
+
+    /// Combines the per-class `score`, with `micro` computing the score of the summed counts
+    fn average<F: Fn(usize) -> f64, G: Fn(usize, usize, usize) -> f64>(&self, average: Average, score: F, micro: G) -> f64 {
+        match average {
+            Average::Binary => score(1),
+            Average::Micro => {
+                let all = 0..self.classes;
+                micro(
+                    all.clone().map(|c| self.true_positives(c)).sum(),
+                    all.clone().map(|c| self.false_positives(c)).sum(),
+                    all.map(|c| self.false_negatives(c)).sum(),
+                )
+            }
+            Average::Macro => (0..self.classes).map(score).sum::<f64>() / self.classes as f64,
+        }
+    }
+}
+
+macro_rules! confusion_metric {
+    ($(#[$doc:meta])* $name:ident, $result:expr) => {
+        $(#[$doc])*
+        pub struct $name<T, B, const N: usize> {
+            matrix: ConfusionMatrix<T, B, N>,
+            average: Average,
+        }
+
+        impl<T, B, const N: usize> $name<T, B, N> {
+            pub fn new(classes: usize, average: Average) -> Self {
+                Self {
+                    matrix: ConfusionMatrix::new(classes),
+                    average,
+                }
+            }
+        }
+
+        impl<T: Number + From<f64>, B: BackendProvider, const N: usize> Metric for $name<T, B, N> {
+            type Input = (Tensor<T, B, N>, Tensor<T, B, N>);
+            type Result = f64;
+
+            fn update(&mut self, state: &Self::Input) {
+                self.matrix.update(state)
+            }
+
+            fn reset(&mut self) {
+                self.matrix.reset()
+            }
+
+            fn result(&self) -> Self::Result {
+                let result: fn(&ConfusionMatrix<T, B, N>, Average) -> f64 = $result;
+                result(&self.matrix, self.average)
+            }
+        }
+    };
+}
+
+confusion_metric!(
+    /// Fraction of the predictions of a class that are correct
+    Precision,
+    |m, average| m.average(average, |c| m.precision_of(c), |tp, fp, _| ratio(tp, fp))
+);
+
+confusion_metric!(
+    /// Fraction of the examples of a class that are predicted correctly
+    Recall,
+    |m, average| m.average(average, |c| m.recall_of(c), |tp, _, fn_| ratio(tp, fn_))
+);
+
+confusion_metric!(
+    /// Harmonic mean of [`Precision`] and [`Recall`]
+    F1Score,
+    |m, average| m.average(
+        average,
+        |c| f1(m.precision_of(c), m.recall_of(c)),
+        |tp, fp, fn_| f1(ratio(tp, fp), ratio(tp, fn_)),
+    )
+);
@@ -1,3 +1,14 @@
+use std::cmp::Ordering;
+
+use num_traits::Number;
+use tensor::{BackendProvider, Tensor};
+
+use crate::layers::fibers;
+
+pub mod accuracy;
+pub mod auc;
+pub mod confusion_matrix;
+
 pub trait Metric {
     type Input;
     type Result;
@@ -79,3 +90,18 @@ impl<M: Metric> Metric for CombinedMetric<M> {
         self.inner.result()
     }
 }
+
+/// Class of every fiber of the last axis
+///
+/// Fibers of length one hold the probability of class `1` of a binary classifier.
+pub(crate) fn classes<T: Number + From<f64>, B: BackendProvider, const N: usize>(tensor: &Tensor<T, B, N>) -> Vec<usize> {
+    fibers(tensor.shape(), Some(N - 1))
+        .map(|mut f| if tensor.shape()[N - 1] == 1 {
+            f.next().map_or(0, |i| (tensor[i] > T::from(0.5)) as usize)
+        } else {
+            f.enumerate()
+                .max_by(|(_, a), (_, b)| tensor[*a].partial_cmp(&tensor[*b]).unwrap_or(Ordering::Equal))
+                .map_or(0, |(k, _)| k)
+        })
+        .collect()
+}
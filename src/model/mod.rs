@@ -62,6 +62,10 @@ impl<M: Layer> Layer for Model<M> {
     fn update(&mut self, update: &Self::Internal) {
         self.model.update(update)
     }
+
+    fn regularization(&self) -> f64 {
+        self.model.regularization()
+    }
 }
 
 // pub struct Input {}
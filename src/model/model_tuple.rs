@@ -130,4 +130,8 @@ impl<L: Layer, M: Layer, I0: Package, O0, O1, O2: Package, I1, O3, O4, O5> Layer
         self.layer.update(&deltas.0);
         self.sub_model.update(&deltas.1);
     }
+
+    fn regularization(&self) -> f64 {
+        self.layer.regularization() + self.sub_model.regularization()
+    }
 }
@@ -49,7 +49,7 @@ pub trait IntoMiniBatch<G>: Sized {
 
 impl<O: Optimizer<G>, G> IntoMiniBatch<G> for O {}
 
-trait Combinable {
+pub(crate) trait Combinable {
     fn combine(a: Self, b: Self) -> Self;
 }
 
@@ -60,12 +60,9 @@ impl<T: AddAssign<T>, B: BackendProvider, const N: usize> Combinable for Tensor<
     }
 }
 
-impl<T: AddAssign<T>, B: BackendProvider, const N: usize> Combinable for [Tensor<T, B, N>; N] {
+impl<A: Combinable, const N: usize> Combinable for [A; N] {
     fn combine(a: Self, b: Self) -> Self {
-        a.zip(b).map(|(mut a, b)| {
-            a += b;
-            a
-        })
+        a.zip(b).map(|(a, b)| Combinable::combine(a, b))
     }
 }
 
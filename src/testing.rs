@@ -0,0 +1,97 @@
+//! Helpers shared by the unit tests
+
+use tensor::{Shape, Tensor, VecProvider};
+
+use crate::{
+    activations::TensorActivation,
+    layers::Layer,
+};
+
+/// Step of the central differences
+const STEP: f64 = 1e-6;
+
+/// Relative tolerance when comparing analytic and numeric values
+const TOLERANCE: f64 = 1e-4;
+
+pub(crate) fn tensor<const N: usize>(shape: [usize; N], values: &[f64]) -> Tensor<f64, VecProvider, N> {
+    assert_eq!(shape.iter().product::<usize>(), values.len());
+    Shape::new(shape).into_tensor(|i| values[i])
+}
+
+/// Deterministic pseudo-random values in `[-1, 1)`
+pub(crate) fn values(n: usize, seed: u64) -> Vec<f64> {
+    let mut state = seed;
+    (0..n)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 2. - 1.
+        })
+        .collect()
+}
+
+pub(crate) fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= TOLERANCE * (1. + expected.abs()),
+        "{actual} is not close to {expected}",
+    );
+}
+
+/// `sum(t * weights)`, a scalar objective whose derivative with respect to `t` is `weights`
+pub(crate) fn weighted_sum<const N: usize>(t: &Tensor<f64, VecProvider, N>, weights: &Tensor<f64, VecProvider, N>) -> f64 {
+    assert_eq!(t.shape(), weights.shape());
+    t.iter().zip(weights.iter()).map(|(t, w)| t * w).sum()
+}
+
+/// Central differences of `f` with respect to every entry of `x`
+pub(crate) fn numeric_gradient<const N: usize, F: Fn(&Tensor<f64, VecProvider, N>) -> f64>(
+    x: &Tensor<f64, VecProvider, N>,
+    f: F,
+) -> Vec<f64> {
+    (0..x.shape().capacity())
+        .map(|i| {
+            let mut plus = x.clone();
+            plus[i] += STEP;
+            let mut minus = x.clone();
+            minus[i] -= STEP;
+            (f(&plus) - f(&minus)) / (2. * STEP)
+        })
+        .collect()
+}
+
+pub(crate) fn assert_gradient<const N: usize>(analytic: &Tensor<f64, VecProvider, N>, numeric: &[f64]) {
+    assert_eq!(analytic.shape().capacity(), numeric.len());
+    analytic.iter().zip(numeric).for_each(|(&a, &n)| assert_close(a, n));
+}
+
+/// Checks the input derivative of `layer` against central differences of a weighted sum of its output
+pub(crate) fn check_input_gradient<
+    L: Layer<
+        Input=Tensor<f64, VecProvider, N>,
+        ReverseInput=Tensor<f64, VecProvider, N>,
+        Output=Tensor<f64, VecProvider, M>,
+        ReverseOutput=Tensor<f64, VecProvider, M>,
+    >,
+    const N: usize,
+    const M: usize,
+>(layer: &L, input: Tensor<f64, VecProvider, N>) {
+    let output_shape = layer.output_shapes().clone();
+    let w = values(output_shape.capacity(), 7);
+    let weights = output_shape.into_tensor(|i| w[i]);
+    let numeric = numeric_gradient(&input, |x| weighted_sum(&layer.feed_forward(x.clone()), &weights));
+    let (_, computation) = layer.back_propagate(input);
+    let (input_d, _) = computation(weights);
+    assert_gradient(&input_d, &numeric);
+}
+
+/// Checks the pre-activation derivative of `activation` against central differences of a weighted sum of its output
+pub(crate) fn check_activation_gradient<A: TensorActivation<f64, VecProvider, N>, const N: usize>(
+    activation: &A,
+    z: Tensor<f64, VecProvider, N>,
+) {
+    let (output, state) = activation.forward(z.clone());
+    let w = values(output.shape().capacity(), 7);
+    let weights = output.shape().clone().into_tensor(|i| w[i]);
+    let numeric = numeric_gradient(&z, |z| weighted_sum(&activation.forward(z.clone()).0, &weights));
+    let (z_d, _) = activation.backward(state, weights);
+    assert_gradient(&z_d, &numeric);
+}
@@ -2,7 +2,7 @@ use crate::{
     datasets::Dataset,
     model::Model,
     optimizers::Optimizer,
-    losses::Loss,
+    losses::{Loss, Scale},
 };
 use crate::layers::Layer;
 
@@ -16,22 +16,39 @@ impl<'m, M: Layer, O: Optimizer<M::Internal>> Trainer<'m, M, O> {
         Self { model, optimizer }
     }
 
-    pub fn train<DS: Dataset<Input=M::Input>, L: Loss<M::Output, M::ReverseOutput>>(
+    /// Trains the model and returns the mean loss of every epoch, including the regularization
+    ///
+    /// The loss and the derivative of every example are scaled by [`Dataset::sample_weight`].
+    /// Models with several outputs can use a tuple or an array of losses, one per output,
+    /// each optionally weighted with [`IntoScaled::scaled`].
+    ///
+    /// [`IntoScaled::scaled`]: crate::losses::scaled::IntoScaled::scaled
+    pub fn train<DS: Dataset<Input=M::Input>, E, L: Loss<M::Output, M::ReverseOutput, E>>(
         &mut self,
         epochs: usize,
         dataset: &DS,
         loss: L,
-        label_to_output: fn(DS::Label) -> M::Output,
-    ) { // todo: add metrics and callbacks, integrate losses into model, return history
-        for _ in 0..epochs {
-            for (input, expected) in dataset.get_training_iter() {
+        label_to_output: fn(DS::Label) -> E,
+    ) -> Vec<f64> where M::ReverseOutput: Scale { // todo: add metrics and callbacks
+        (0..epochs).map(|_| {
+            let mut total = 0.;
+            let mut count = 0;
+            for (index, (input, label)) in dataset.get_training_iter().enumerate() {
+                let weight = dataset.sample_weight(index, &input, &label);
                 let (predicted, computation) = self.model.back_propagate(input);
-                let derivatives = loss.derive(&predicted, &label_to_output(expected));
+                let expected = label_to_output(label);
+                total += weight * loss.loss(&predicted, &expected);
+                count += 1;
+                let mut derivatives = loss.derive(&predicted, &expected);
+                if weight != 1. {
+                    derivatives.scale(weight);
+                }
                 let (_, gradients) = computation(derivatives);
                 if let Some(deltas) = self.optimizer.gradients_to_deltas(gradients) {
                     self.model.update(&deltas);
                 }
             }
-        }
+            total / count.max(1) as f64 + self.model.regularization()
+        }).collect()
     }
 }